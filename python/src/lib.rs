@@ -1,9 +1,11 @@
 use lin_rado_turing::{
-    machine::{run_machine, HaltReason, Machine},
-    program::{parse_program, ProgramT},
+    machine::{HaltReason, Machine},
+    pipeline::Pipeline,
+    program::{parse_program, Program, ProgramT},
     types::{State, Symbol},
 };
 use pyo3::{exceptions::PyValueError, prelude::*};
+use std::collections::BTreeMap;
 
 #[pymodule]
 fn tm(_py: Python, m: &PyModule) -> PyResult<()> {
@@ -11,6 +13,16 @@ fn tm(_py: Python, m: &PyModule) -> PyResult<()> {
     Ok(())
 }
 
+/// Run a program through the pipeline on a plain tape.
+fn run<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>(
+    pipeline: &Pipeline,
+    program: &Program<S, Sym>,
+) -> Machine<S, Sym> {
+    pipeline.run(program).machine
+}
+
+/// Run a program with the recurrence and blank checks given, or through
+/// `deciders`, a pipeline as `turing --deciders` takes, if there is one.
 #[pyfunction]
 pub fn run_bb(
     prog: &str,
@@ -19,89 +31,37 @@ pub fn run_bb(
     check_rec: Option<usize>,
     check_blanks: bool,
     _samples: Vec<i64>,
+    deciders: Option<String>,
 ) -> PyResult<PyMachine> {
-    let blank = if check_blanks { Some(0) } else { None };
+    let pipeline = match deciders.as_deref() {
+        Some(spec) => Pipeline::parse(spec, x_limit).map_err(PyValueError::new_err)?,
+        None => {
+            let blank = if check_blanks { Some(0) } else { None };
+            Pipeline::from_checks(x_limit, check_rec, blank, false)
+        }
+    };
 
     let program = match parse_program(prog) {
         Ok(p) => p,
         Err(e) => return Err(PyValueError::new_err(e.0)),
     };
     match program {
-        ProgramT::TwoTwo(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::TwoThree(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::TwoFour(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::ThreeTwo(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::ThreeThree(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::ThreeFour(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::FourTwo(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::FourThree(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::FourFour(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::FiveTwo(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::FiveThree(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::FiveFour(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::SixTwo(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::SixThree(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
-        ProgramT::SixFour(program) => Ok((
-            prog,
-            run_machine(program, prog, x_limit, None, false, check_rec, blank, false),
-        )
-            .into()),
+        ProgramT::TwoTwo(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::TwoThree(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::TwoFour(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::ThreeTwo(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::ThreeThree(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::ThreeFour(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::FourTwo(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::FourThree(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::FourFour(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::FiveTwo(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::FiveThree(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::FiveFour(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::SixTwo(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::SixThree(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::SixFour(program) => Ok((prog, run(&pipeline, &program)).into()),
+        ProgramT::Dynamic(program) => Ok((prog, run(&pipeline, &program)).into()),
     }
 }
 
@@ -133,6 +93,36 @@ pub struct MachineResult {
     blanks: Option<usize>,
     #[pyo3(get)]
     xlimit: Option<usize>,
+    /// The period of a translated cycler, and how far it moves each period.
+    #[pyo3(get)]
+    trcycl: Option<(usize, i64)>,
+    /// The steps between a bouncer's first sweeps, and the words its tape
+    /// grows by each sweep.
+    #[pyo3(get)]
+    bouncer: Option<(usize, Vec<String>)>,
+    /// The period of a machine that comes back to the same configuration.
+    #[pyo3(get)]
+    cycler: Option<usize>,
+    /// The most steps back a halt could be traced, if backward reasoning
+    /// proved it never halts.
+    #[pyo3(get)]
+    backward: Option<usize>,
+    /// The most states of the automata of a closed tape language proving it
+    /// never halts.
+    #[pyo3(get)]
+    ctl: Option<usize>,
+    /// The number of cells the head has been over.
+    #[pyo3(get)]
+    cells: usize,
+    /// The leftmost and rightmost positions of the head.
+    #[pyo3(get)]
+    span: (i64, i64),
+    /// Where the head finished, relative to where it started.
+    #[pyo3(get)]
+    head: i64,
+    /// The number of steps taken from each state.
+    #[pyo3(get)]
+    visits: BTreeMap<String, usize>,
 }
 
 impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>
@@ -140,57 +130,48 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>
 {
     fn from((prog_str, other): (&str, Machine<S, Sym>)) -> Self {
         let halt = other.halt().expect("Machine has been run until halt");
-        let result = match &halt.reason {
-            HaltReason::Quasihalt(period) => MachineResult {
-                prog: prog_str.into(),
-                undfnd: None,
-                linrec: None,
-                qsihlt: Some(*period),
-                blanks: None,
-                xlimit: None,
-            },
-            HaltReason::Recurr(period) => MachineResult {
-                prog: prog_str.into(),
-                undfnd: None,
-                linrec: Some(*period),
-                qsihlt: None,
-                blanks: None,
-                xlimit: None,
-            },
-            HaltReason::Blanking => MachineResult {
-                prog: prog_str.into(),
-                undfnd: None,
-                linrec: None,
-                qsihlt: None,
-                blanks: Some(halt.steps),
-                xlimit: None,
-            },
-            HaltReason::XLimit => MachineResult {
-                prog: prog_str.into(),
-                undfnd: None,
-                linrec: None,
-                qsihlt: None,
-                blanks: None,
-                xlimit: Some(halt.steps),
-            },
-            HaltReason::Undefined(state_color) => MachineResult {
-                prog: prog_str.into(),
-                undfnd: Some((halt.steps, state_color.to_owned())),
-                linrec: None,
-                qsihlt: None,
-                blanks: None,
-                xlimit: None,
-            },
-            HaltReason::Halt => MachineResult {
-                prog: prog_str.into(),
-                undfnd: None,
-                linrec: None,
-                qsihlt: None,
-                blanks: None,
-                xlimit: None,
-            },
+        let space = other.space();
+
+        let mut result = MachineResult {
+            prog: prog_str.into(),
+            undfnd: None,
+            linrec: None,
+            qsihlt: None,
+            blanks: None,
+            xlimit: None,
+            trcycl: None,
+            bouncer: None,
+            cycler: None,
+            backward: None,
+            ctl: None,
+            cells: space.cells(),
+            span: (space.leftmost, space.rightmost),
+            head: other.head(),
+            visits: space
+                .visits
+                .iter()
+                .map(|(s, n)| (s.to_string(), *n))
+                .collect(),
         };
 
+        match &halt.reason {
+            HaltReason::Quasihalt(period) => result.qsihlt = Some(*period),
+            HaltReason::Recurr(period) => result.linrec = Some(*period),
+            HaltReason::Blanking => result.blanks = Some(halt.steps),
+            HaltReason::XLimit => result.xlimit = Some(halt.steps),
+            HaltReason::Undefined(state_color) => {
+                result.undfnd = Some((halt.steps, state_color.to_owned()))
+            }
+            HaltReason::TranslatedCycler(period, offset) => {
+                result.trcycl = Some((*period, *offset))
+            }
+            HaltReason::Bouncer(period, words) => result.bouncer = Some((*period, words.clone())),
+            HaltReason::Cycler(period) => result.cycler = Some(*period),
+            HaltReason::Backward(depth) => result.backward = Some(*depth),
+            HaltReason::ClosedLanguage(states) => result.ctl = Some(*states),
+            HaltReason::Halt => (),
+        }
+
         PyMachine::new(result)
     }
 }
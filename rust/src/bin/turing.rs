@@ -1,18 +1,74 @@
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use lin_rado_turing::{
-    machine::run_machine,
-    program::{parse_program, ProgramParseError, ProgramT},
+    certificate::Certificate,
+    checkpoint::{Checkpoint, CheckpointError, RunOptions},
+    decider::{
+        backward::{self, backward_reasoning, Backward},
+        closed_language::{self, find_closed_language},
+    },
+    diagram::{Diagram, DiagramOptions},
+    machine::Machine,
+    macro_machine::run_macro_machine,
+    pipeline::{Pipeline, Verdict},
+    program::{parse_dynamic_program, parse_program, Program, ProgramParseError, ProgramT},
+    report::{write_error, write_header, Format, Report},
+    results::{Record, ResultsDb, ResultsError},
+    tape::{RleTape, Tape, VecTape},
+    types::{State, Symbol},
 };
+use rayon::prelude::*;
 use std::{
-    fs::OpenOptions,
-    io::{BufWriter, Write},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
     process::exit,
     str::FromStr,
+    time::Instant,
 };
 
 fn main() {
     let args = parse_args();
 
+    if let Some(args) = args.subcommand_matches("resume") {
+        resume(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("batch") {
+        batch(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("diagram") {
+        diagram(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("dot") {
+        dot(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("ctl") {
+        ctl(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("backward") {
+        backward(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("verify") {
+        verify(args);
+        return;
+    }
+
+    if let Some(args) = args.subcommand_matches("db") {
+        db(args);
+        return;
+    }
+
     let prog_str = args.value_of("program").expect("program is required");
 
     let program = match parse_program(prog_str) {
@@ -28,34 +84,160 @@ fn main() {
         }
     };
 
-    let check = match args.value_of("check-recurrence") {
-        Some(s) => match s.parse() {
-            Ok(check) => Some(check),
-            Err(e) => {
-                writeln!(std::io::stderr(), "Error parsing check-recurrence: {}", e)
-                    .expect("Unable to write to stderr");
-                exit(1);
-            }
-        },
-        None => None,
-    };
+    let check = parse_check(&args);
 
-    let blank = match args.value_of("blank") {
+    let blank = parse_blank(&args);
+
+    let parallel = args.is_present("parallel");
+
+    let verbose = args.is_present("verbose");
+
+    let accelerate = args.is_present("accelerate");
+
+    let output = parse_output(&args);
+
+    let limit = parse_limit(&args).unwrap_or(10000);
+
+    let block = match args.value_of("block") {
         Some(s) => match s.parse() {
-            Ok(blank) => Some(blank),
+            Ok(0) => {
+                eprintln!("Error parsing --block: blocks must be at least one cell");
+                exit(1)
+            }
+            Ok(block) => Some(block),
             Err(e) => {
-                eprintln!("Error parsing --blank: {}", e);
+                eprintln!("Error parsing --block: {}", e);
                 exit(1)
             }
         },
         None => None,
     };
 
-    let parallel = args.is_present("parallel");
+    if check.is_some() && block.is_some() {
+        eprintln!("Error: --check can't be used with --block");
+        exit(1)
+    }
 
-    let verbose = args.is_present("verbose");
+    if blank.is_some() && block.is_some() {
+        eprintln!("Error: --blank can't be used with --block");
+        exit(1)
+    }
+
+    if parallel && block.is_some() {
+        eprintln!("Error: --parallel can't be used with --block");
+        exit(1)
+    }
+
+    if accelerate && block.is_some() {
+        eprintln!("Error: --accelerate can't be used with --block");
+        exit(1)
+    }
+
+    if args.is_present("deciders") && block.is_some() {
+        eprintln!("Error: --deciders can't be used with --block");
+        exit(1)
+    }
+
+    let pipeline = parse_pipeline(&args, limit, parallel).accelerate(accelerate);
+
+    if verbose && block.is_none() && pipeline.run_options().is_none() {
+        eprintln!("Error: --verbose needs a single simulate, blanking or recurrence stage");
+        exit(1)
+    }
+
+    let checkpoint = args.value_of("checkpoint").map(str::to_string);
 
-    let output: Option<Box<dyn Write>> = match args.value_of("output") {
+    let checkpoint_every = parse_checkpoint_every(&args);
+
+    if checkpoint.is_some() && block.is_some() {
+        eprintln!("Error: --checkpoint can't be used with --block");
+        exit(1)
+    }
+
+    if checkpoint.is_some() && pipeline.run_options().is_none() {
+        eprintln!(
+            "Error: --checkpoint needs a single simulate, blanking or recurrence stage \
+             without a time budget"
+        );
+        exit(1)
+    }
+
+    let format = parse_format(&args);
+
+    if format != Format::Text && block.is_some() {
+        eprintln!("Error: --format can't be used with --block");
+        exit(1)
+    }
+
+    let space = args.is_present("space");
+
+    if space && block.is_some() {
+        eprintln!("Error: --space can't be used with --block");
+        exit(1)
+    }
+
+    let certificate = args.value_of("certificate").map(str::to_string);
+
+    if certificate.is_some() && block.is_some() {
+        eprintln!("Error: --certificate can't be used with --block");
+        exit(1)
+    }
+
+    let options = Options {
+        program: prog_str.to_string(),
+        limit,
+        output,
+        verbose,
+        pipeline,
+        block,
+        accelerate,
+        checkpoint,
+        checkpoint_every,
+        format,
+        space,
+        certificate,
+    };
+
+    match program {
+        ProgramT::TwoTwo(program) => run(program, options),
+        ProgramT::TwoThree(program) => run(program, options),
+        ProgramT::TwoFour(program) => run(program, options),
+        ProgramT::ThreeTwo(program) => run(program, options),
+        ProgramT::ThreeThree(program) => run(program, options),
+        ProgramT::ThreeFour(program) => run(program, options),
+        ProgramT::FourTwo(program) => run(program, options),
+        ProgramT::FourThree(program) => run(program, options),
+        ProgramT::FourFour(program) => run(program, options),
+        ProgramT::FiveTwo(program) => run(program, options),
+        ProgramT::FiveThree(program) => run(program, options),
+        ProgramT::FiveFour(program) => run(program, options),
+        ProgramT::SixTwo(program) => run(program, options),
+        ProgramT::SixThree(program) => run(program, options),
+        ProgramT::SixFour(program) => run(program, options),
+        ProgramT::Dynamic(program) => run(program, options),
+    }
+}
+
+struct Options {
+    /// The program as it was given, which the summary echoes.
+    program: String,
+    limit: usize,
+    output: Option<Box<dyn Write>>,
+    verbose: bool,
+    /// How to decide the program, unless it's run as a macro machine.
+    pipeline: Pipeline,
+    block: Option<usize>,
+    accelerate: bool,
+    checkpoint: Option<String>,
+    checkpoint_every: Option<usize>,
+    format: Format,
+    space: bool,
+    /// Where to write the certificate if the run ends in a recurrence.
+    certificate: Option<String>,
+}
+
+fn parse_output(args: &ArgMatches) -> Option<Box<dyn Write>> {
+    match args.value_of("output") {
         Some(o) => match o {
             "-" => Some(Box::new(BufWriter::with_capacity(1_000, std::io::stdout()))),
             a => match OpenOptions::new().append(true).create(true).open(a) {
@@ -68,143 +250,1193 @@ fn main() {
             },
         },
         None => None,
-    };
+    }
+}
 
-    let limit = match args
-        .value_of("limit")
-        .map_or_else(|| Ok(10000), <usize as FromStr>::from_str)
-    {
-        Ok(l) => l,
-        Err(e) => {
+fn parse_limit(args: &ArgMatches) -> Option<usize> {
+    match args.value_of("limit").map(<usize as FromStr>::from_str) {
+        Some(Ok(l)) => Some(l),
+        Some(Err(e)) => {
             writeln!(std::io::stderr(), "Error parsing limit: {}", e)
                 .expect("Unable to write to stderr");
             exit(1);
         }
+        None => None,
+    }
+}
+
+fn parse_format(args: &ArgMatches) -> Format {
+    match args.value_of("format").map(str::parse) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            eprintln!("Error parsing --format: {}", e);
+            exit(1)
+        }
+        None => Format::Text,
+    }
+}
+
+fn parse_check(args: &ArgMatches) -> Option<usize> {
+    match args.value_of("check-recurrence") {
+        Some(s) => match s.parse() {
+            Ok(check) => Some(check),
+            Err(e) => {
+                writeln!(std::io::stderr(), "Error parsing check-recurrence: {}", e)
+                    .expect("Unable to write to stderr");
+                exit(1);
+            }
+        },
+        None => None,
+    }
+}
+
+fn parse_blank(args: &ArgMatches) -> Option<usize> {
+    match args.value_of("blank") {
+        Some(s) => match s.parse() {
+            Ok(blank) => Some(blank),
+            Err(e) => {
+                eprintln!("Error parsing --blank: {}", e);
+                exit(1)
+            }
+        },
+        None => None,
+    }
+}
+
+/// The deciders given with `--deciders`, or the checks given with `--check`
+/// and `--blank`, run in parallel if `parallel`.
+fn parse_pipeline(args: &ArgMatches, limit: usize, parallel: bool) -> Pipeline {
+    match args.value_of("deciders") {
+        Some(spec) => match Pipeline::parse(spec, limit) {
+            Ok(pipeline) => pipeline,
+            Err(e) => {
+                eprintln!("Error parsing --deciders: {}", e);
+                exit(1)
+            }
+        },
+        None => Pipeline::from_checks(limit, parse_check(args), parse_blank(args), parallel),
+    }
+}
+
+fn parse_checkpoint_every(args: &ArgMatches) -> Option<usize> {
+    match args.value_of("checkpoint-every") {
+        Some(s) => match s.parse() {
+            Ok(0) => {
+                eprintln!("Error parsing --checkpoint-every: must be at least one step");
+                exit(1)
+            }
+            Ok(every) => Some(every),
+            Err(e) => {
+                eprintln!("Error parsing --checkpoint-every: {}", e);
+                exit(1)
+            }
+        },
+        None => None,
+    }
+}
+
+fn run<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+    program: Program<S, Sym>,
+    options: Options,
+) {
+    if let Some(block) = options.block {
+        run_macro_machine(
+            program,
+            &options.program,
+            block,
+            options.limit,
+            options.output,
+            options.verbose,
+        );
+    } else if let Some(run_options) = options.pipeline.run_options() {
+        if run_options.accelerate {
+            let machine = Machine::with_tape(program, RleTape::default());
+            run_checkpointed(machine, run_options, options);
+        } else {
+            run_checkpointed(Machine::new(program), run_options, options);
+        }
+    } else if options.accelerate {
+        run_pipeline::<S, Sym, RleTape<Sym>>(program, options);
+    } else {
+        run_pipeline::<S, Sym, VecTape<Sym>>(program, options);
+    }
+}
+
+/// Run every stage of the pipeline until one is conclusive.
+fn run_pipeline<S: State + Send + Sync, Sym: Symbol + Send + Sync, T: Tape<Sym> + Send + Sync>(
+    program: Program<S, Sym>,
+    options: Options,
+) {
+    let Options {
+        program: prog_str,
+        mut output,
+        pipeline,
+        format,
+        space,
+        certificate,
+        ..
+    } = options;
+
+    let start = Instant::now();
+    let verdict = pipeline.run::<S, Sym, T>(&program);
+
+    if let Some(w) = &mut output {
+        let report = Report {
+            program: prog_str,
+            ..verdict.report(start.elapsed())
+        };
+        write_report(w, &report, format, space);
+    }
+
+    if let Some(path) = &certificate {
+        write_certificate(path, &verdict.machine);
+    }
+}
+
+/// Run in stretches of `checkpoint_every` steps, saving a checkpoint after
+/// each one and when the limit is reached, if there's a checkpoint file.
+fn run_checkpointed<
+    S: State + Send + Sync,
+    Sym: Symbol + Send + Sync,
+    T: Tape<Sym> + Send + Sync,
+>(
+    mut machine: Machine<S, Sym, T>,
+    run_options: RunOptions,
+    options: Options,
+) {
+    let Options {
+        program: prog_str,
+        mut output,
+        verbose,
+        pipeline,
+        checkpoint,
+        checkpoint_every,
+        format,
+        space,
+        certificate,
+        ..
+    } = options;
+    let RunOptions {
+        limit,
+        check_recurrence: check,
+        check_blank: blank,
+        parallel,
+        accelerate,
+    } = run_options.clone();
+
+    let start = Instant::now();
+
+    loop {
+        let next = checkpoint_every.map_or(limit, |every| {
+            machine.steps().saturating_add(every).min(limit)
+        });
+
+        if verbose {
+            machine.run_until_halt(
+                vec![],
+                next,
+                &mut output,
+                check,
+                blank,
+                parallel,
+                accelerate,
+            );
+        } else {
+            machine.run_until_halt::<std::io::Stdout>(
+                vec![],
+                next,
+                &mut None,
+                check,
+                blank,
+                parallel,
+                accelerate,
+            );
+        }
+
+        if !machine.halt().is_some_and(|h| h.is_limit()) {
+            break;
+        }
+
+        if let Some(path) = &checkpoint {
+            let saved = machine
+                .checkpoint(true)
+                .with_options(run_options.clone())
+                .save(path);
+            if let Err(CheckpointError(e)) = saved {
+                eprintln!("Error saving checkpoint: {}", e);
+                exit(1)
+            }
+        }
+
+        if next >= limit {
+            break;
+        }
+    }
+
+    let conclusive = !machine.halt().is_some_and(|h| h.is_limit());
+    let verdict = Verdict {
+        decider: pipeline
+            .stages()
+            .first()
+            .map(|stage| stage.decider)
+            .filter(|_| conclusive),
+        machine,
+    };
+
+    if let Some(w) = &mut output {
+        let report = Report {
+            program: prog_str,
+            ..verdict.report(start.elapsed())
+        };
+        write_report(w, &report, format, space);
+    }
+
+    if let Some(path) = &certificate {
+        write_certificate(path, &verdict.machine);
+    }
+}
+
+/// Write the certificate for a run that ended in a recurrence as JSON.
+fn write_certificate<
+    S: State + Send + Sync,
+    Sym: Symbol + Send + Sync,
+    T: Tape<Sym> + Send + Sync,
+>(
+    path: &str,
+    machine: &Machine<S, Sym, T>,
+) {
+    let halt = machine.halt().expect("The run has stopped");
+
+    let certificate = match Certificate::new(machine.program(), halt) {
+        Ok(certificate) => certificate,
+        Err(e) => {
+            eprintln!("No certificate written: {}", e);
+            return;
+        }
+    };
+
+    let written = File::create(path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer(&mut writer, &certificate).map_err(|e| e.to_string())?;
+            writer.flush().map_err(|e| e.to_string())
+        });
+    if let Err(e) = written {
+        eprintln!("Error writing certificate: {}", e);
+        exit(1)
+    }
+}
+
+fn write_report(w: &mut Box<dyn Write>, report: &Report, format: Format, space: bool) {
+    let written = write_header(w, format)
+        .and_then(|_| report.write(w, format))
+        .and_then(|_| match format {
+            Format::Text if space => report.write_space(w),
+            _ => Ok(()),
+        });
+    if let Err(e) = written {
+        eprintln!("Error writing: {}", e);
+    }
+}
+
+fn resume(args: &ArgMatches) {
+    let path = args
+        .value_of("checkpoint")
+        .expect("checkpoint is required")
+        .to_string();
+
+    let checkpoint = match Checkpoint::load(&path) {
+        Ok(c) => c,
+        Err(CheckpointError(e)) => {
+            eprintln!("Error loading checkpoint: {}", e);
+            exit(1)
+        }
+    };
+
+    let run_options = match &checkpoint.options {
+        Some(o) => o.clone(),
+        None => {
+            eprintln!("Error loading checkpoint: it doesn't say how the run was started");
+            exit(1)
+        }
+    };
+
+    let program = match parse_program(&checkpoint.program) {
+        Ok(p) => p,
+        Err(ProgramParseError(msg)) => {
+            eprintln!("Error parsing program in checkpoint: {}", msg);
+            exit(1)
+        }
+    };
+
+    let limit = parse_limit(args).unwrap_or(run_options.limit);
+    let pipeline = Pipeline::from_checks(
+        limit,
+        run_options.check_recurrence,
+        run_options.check_blank,
+        run_options.parallel,
+    )
+    .accelerate(run_options.accelerate);
+
+    let options = Options {
+        program: checkpoint.program.clone(),
+        limit,
+        output: parse_output(args),
+        verbose: args.is_present("verbose"),
+        pipeline,
+        block: None,
+        accelerate: run_options.accelerate,
+        checkpoint: Some(path),
+        checkpoint_every: parse_checkpoint_every(args),
+        format: parse_format(args),
+        space: args.is_present("space"),
+        certificate: None,
     };
 
     match program {
-        ProgramT::TwoTwo(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+        ProgramT::TwoTwo(program) => resume_run(program, &checkpoint, options),
+        ProgramT::TwoThree(program) => resume_run(program, &checkpoint, options),
+        ProgramT::TwoFour(program) => resume_run(program, &checkpoint, options),
+        ProgramT::ThreeTwo(program) => resume_run(program, &checkpoint, options),
+        ProgramT::ThreeThree(program) => resume_run(program, &checkpoint, options),
+        ProgramT::ThreeFour(program) => resume_run(program, &checkpoint, options),
+        ProgramT::FourTwo(program) => resume_run(program, &checkpoint, options),
+        ProgramT::FourThree(program) => resume_run(program, &checkpoint, options),
+        ProgramT::FourFour(program) => resume_run(program, &checkpoint, options),
+        ProgramT::FiveTwo(program) => resume_run(program, &checkpoint, options),
+        ProgramT::FiveThree(program) => resume_run(program, &checkpoint, options),
+        ProgramT::FiveFour(program) => resume_run(program, &checkpoint, options),
+        ProgramT::SixTwo(program) => resume_run(program, &checkpoint, options),
+        ProgramT::SixThree(program) => resume_run(program, &checkpoint, options),
+        ProgramT::SixFour(program) => resume_run(program, &checkpoint, options),
+        ProgramT::Dynamic(program) => resume_run(program, &checkpoint, options),
+    }
+}
+
+fn resume_run<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+    program: Program<S, Sym>,
+    checkpoint: &Checkpoint,
+    options: Options,
+) {
+    let run_options = options
+        .pipeline
+        .run_options()
+        .expect("A resumed run is a single stage");
+    let restored = if options.accelerate {
+        Machine::<S, Sym, RleTape<Sym>>::restore(program, checkpoint)
+            .map(|machine| run_checkpointed(machine, run_options, options))
+    } else {
+        Machine::<S, Sym>::restore(program, checkpoint)
+            .map(|machine| run_checkpointed(machine, run_options, options))
+    };
+    if let Err(CheckpointError(e)) = restored {
+        eprintln!("Error loading checkpoint: {}", e);
+        exit(1)
+    }
+}
+
+/// How many programs are read before running them, so that a parallel
+/// batch keeps its input order without holding the whole input.
+const BATCH_CHUNK: usize = 1024;
+
+#[derive(Clone)]
+struct BatchOptions {
+    pipeline: Pipeline,
+    accelerate: bool,
+    format: Format,
+}
+
+/// Run every program in the input, one per line, writing a summary line for
+/// each in the same order.
+fn batch(args: &ArgMatches) {
+    let input: Box<dyn BufRead> = match args.value_of("input") {
+        None | Some("-") => Box::new(BufReader::new(std::io::stdin())),
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Failed to open file: {}", e);
+                exit(1)
+            }
+        },
+    };
+
+    let mut output = parse_output(args)
+        .unwrap_or_else(|| Box::new(BufWriter::with_capacity(1_000, std::io::stdout())));
+
+    let limit = parse_limit(args).unwrap_or(10000);
+    let accelerate = args.is_present("accelerate");
+
+    // Here --parallel runs programs in parallel, not the recurrence check.
+    let pipeline = parse_pipeline(args, limit, false);
+
+    let options = BatchOptions {
+        pipeline: pipeline.accelerate(accelerate),
+        accelerate,
+        format: parse_format(args),
+    };
+    let parallel = args.is_present("parallel");
+
+    if let Err(e) = write_header(&mut output, options.format) {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+
+    let mut lines = input.lines();
+    loop {
+        let mut chunk = vec![];
+        for line in lines.by_ref() {
+            match line {
+                Ok(line) if line.trim().is_empty() => (),
+                Ok(line) => chunk.push(line.trim().to_string()),
+                Err(e) => {
+                    eprintln!("Error reading programs: {}", e);
+                    exit(1)
+                }
+            }
+            if chunk.len() == BATCH_CHUNK {
+                break;
+            }
         }
-        ProgramT::TwoThree(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+        if chunk.is_empty() {
+            break;
         }
-        ProgramT::TwoFour(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+
+        let results: Vec<String> = if parallel {
+            chunk
+                .par_iter()
+                .map(|prog_str| batch_run(prog_str, &options))
+                .collect()
+        } else {
+            chunk
+                .iter()
+                .map(|prog_str| batch_run(prog_str, &options))
+                .collect()
+        };
+
+        for result in results {
+            if let Err(e) = output.write_all(result.as_bytes()) {
+                eprintln!("Error writing: {}", e);
+                exit(1)
+            }
         }
-        ProgramT::ThreeTwo(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    }
+
+    if let Err(e) = output.flush() {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+}
+
+/// The summary line for a single program of a batch.
+fn batch_run(prog_str: &str, options: &BatchOptions) -> String {
+    match parse_program(prog_str) {
+        Ok(ProgramT::TwoTwo(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::TwoThree(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::TwoFour(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::ThreeTwo(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::ThreeThree(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::ThreeFour(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::FourTwo(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::FourThree(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::FourFour(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::FiveTwo(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::FiveThree(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::FiveFour(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::SixTwo(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::SixThree(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::SixFour(program)) => batch_summary(program, prog_str, options),
+        Ok(ProgramT::Dynamic(program)) => batch_summary(program, prog_str, options),
+        Err(ProgramParseError(msg)) => {
+            let mut error = vec![];
+            write_error(&mut error, options.format, prog_str, &msg)
+                .expect("Writing to a Vec can't fail");
+            String::from_utf8(error).expect("Results are UTF-8")
         }
-        ProgramT::ThreeThree(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    }
+}
+
+fn batch_summary<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+    program: Program<S, Sym>,
+    prog_str: &str,
+    options: &BatchOptions,
+) -> String {
+    let start = Instant::now();
+    let report = if options.accelerate {
+        let verdict = options.pipeline.run::<S, Sym, RleTape<Sym>>(&program);
+        verdict.report(start.elapsed())
+    } else {
+        let verdict = options.pipeline.run::<S, Sym, VecTape<Sym>>(&program);
+        verdict.report(start.elapsed())
+    };
+    let report = Report {
+        program: prog_str.to_string(),
+        ..report
+    };
+
+    let mut summary = vec![];
+    report
+        .write(&mut summary, options.format)
+        .expect("Writing to a Vec can't fail");
+
+    String::from_utf8(summary).expect("Results are UTF-8")
+}
+
+/// Draw a space-time diagram of a program to a PPM or PNG file, chosen by
+/// the file's extension.
+fn diagram(args: &ArgMatches) {
+    let prog_str = args.value_of("program").expect("program is required");
+    let path = args.value_of("file").expect("file is required");
+
+    let png = match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("png") => true,
+        Some("ppm") => false,
+        _ => {
+            eprintln!("Error: the diagram file must end in .png or .ppm");
+            exit(1)
         }
-        ProgramT::ThreeFour(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    };
+
+    let every = match args.value_of("every").map(str::parse) {
+        Some(Ok(0)) => {
+            eprintln!("Error parsing --every: must be at least one step");
+            exit(1)
         }
-        ProgramT::FourTwo(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+        Some(Ok(every)) => every,
+        Some(Err(e)) => {
+            eprintln!("Error parsing --every: {}", e);
+            exit(1)
         }
-        ProgramT::FourThree(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+        None => 1,
+    };
+
+    let options = DiagramOptions {
+        limit: parse_limit(args).unwrap_or(1000),
+        every,
+        color_states: args.is_present("states"),
+    };
+
+    let diagram = match parse_program(prog_str) {
+        Ok(ProgramT::TwoTwo(program)) => draw(program, options),
+        Ok(ProgramT::TwoThree(program)) => draw(program, options),
+        Ok(ProgramT::TwoFour(program)) => draw(program, options),
+        Ok(ProgramT::ThreeTwo(program)) => draw(program, options),
+        Ok(ProgramT::ThreeThree(program)) => draw(program, options),
+        Ok(ProgramT::ThreeFour(program)) => draw(program, options),
+        Ok(ProgramT::FourTwo(program)) => draw(program, options),
+        Ok(ProgramT::FourThree(program)) => draw(program, options),
+        Ok(ProgramT::FourFour(program)) => draw(program, options),
+        Ok(ProgramT::FiveTwo(program)) => draw(program, options),
+        Ok(ProgramT::FiveThree(program)) => draw(program, options),
+        Ok(ProgramT::FiveFour(program)) => draw(program, options),
+        Ok(ProgramT::SixTwo(program)) => draw(program, options),
+        Ok(ProgramT::SixThree(program)) => draw(program, options),
+        Ok(ProgramT::SixFour(program)) => draw(program, options),
+        Ok(ProgramT::Dynamic(program)) => draw(program, options),
+        Err(ProgramParseError(msg)) => {
+            eprintln!("Error parsing program or complexity: {}", msg);
+            exit(1)
         }
-        ProgramT::FourFour(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    };
+
+    let written = File::create(path).and_then(|file| {
+        let mut w = BufWriter::new(file);
+        if png {
+            diagram.write_png(&mut w)?;
+        } else {
+            diagram.write_ppm(&mut w)?;
         }
-        ProgramT::FiveTwo(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+        w.flush()
+    });
+    if let Err(e) = written {
+        eprintln!("Error writing diagram: {}", e);
+        exit(1)
+    }
+}
+
+fn draw<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+    program: Program<S, Sym>,
+    options: DiagramOptions,
+) -> Diagram {
+    Diagram::draw(&mut Machine::new(program), options)
+}
+
+/// Write a program's state graph in Graphviz DOT.
+fn dot(args: &ArgMatches) {
+    let prog_str = args.value_of("program").expect("program is required");
+
+    let program = match parse_program(prog_str) {
+        Ok(p) if args.is_present("normalize") => p.normalize(true),
+        Ok(p) => p,
+        Err(ProgramParseError(msg)) => {
+            eprintln!("Error parsing program or complexity: {}", msg);
+            exit(1)
         }
-        ProgramT::FiveThree(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    };
+
+    let mut output = parse_output(args)
+        .unwrap_or_else(|| Box::new(BufWriter::with_capacity(1_000, std::io::stdout())));
+
+    if let Err(e) = output
+        .write_all(program.to_dot().as_bytes())
+        .and_then(|_| output.flush())
+    {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+}
+
+fn ctl(args: &ArgMatches) {
+    let prog_str = args.value_of("program").expect("program is required");
+
+    let program = match parse_dynamic_program(prog_str) {
+        Ok(p) => p,
+        Err(ProgramParseError(msg)) => {
+            eprintln!("Error parsing program or complexity: {}", msg);
+            exit(1)
         }
-        ProgramT::FiveFour(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    };
+
+    let max_states = match args.value_of("states").map(str::parse) {
+        Some(Ok(states)) => states,
+        Some(Err(e)) => {
+            eprintln!("Error parsing --states: {}", e);
+            exit(1)
         }
-        ProgramT::SixTwo(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
+        None => 2,
+    };
+
+    let certificate = match find_closed_language(&program, max_states, None) {
+        Some(certificate) => certificate,
+        None => {
+            eprintln!(
+                "No closed tape language found with up to {} states",
+                max_states
             );
+            exit(1)
         }
-        ProgramT::SixThree(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    };
+
+    let mut output = parse_output(args)
+        .unwrap_or_else(|| Box::new(BufWriter::with_capacity(1_000, std::io::stdout())));
+
+    let json = serde_json::to_string(&certificate).expect("Certificates are always serializable");
+
+    if let Err(e) = writeln!(output, "{}", json).and_then(|_| output.flush()) {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+}
+
+/// Reason backwards from the halting instructions, writing the proof that
+/// none of them can be reached.
+fn backward(args: &ArgMatches) {
+    let prog_str = args.value_of("program").expect("program is required");
+
+    let program = match parse_dynamic_program(prog_str) {
+        Ok(p) => p,
+        Err(ProgramParseError(msg)) => {
+            eprintln!("Error parsing program or complexity: {}", msg);
+            exit(1)
         }
-        ProgramT::SixFour(program) => {
-            run_machine(
-                program, prog_str, limit, output, verbose, check, blank, parallel,
-            );
+    };
+
+    let depth = match args.value_of("depth").map(str::parse) {
+        Some(Ok(depth)) => depth,
+        Some(Err(e)) => {
+            eprintln!("Error parsing --depth: {}", e);
+            exit(1)
+        }
+        None => 30,
+    };
+
+    let proof = match backward_reasoning(&program, depth, None) {
+        Backward::NonHalting(proof) => proof,
+        Backward::Unknown => {
+            eprintln!("No proof found reasoning back up to {} steps", depth);
+            exit(1)
+        }
+    };
+
+    let mut output = parse_output(args)
+        .unwrap_or_else(|| Box::new(BufWriter::with_capacity(1_000, std::io::stdout())));
+
+    let json = serde_json::to_string(&proof).expect("Proofs are always serializable");
+
+    if let Err(e) = writeln!(output, "{}", json).and_then(|_| output.flush()) {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+}
+
+/// Check a certificate that a program never halts, for a recurrence, a
+/// closed tape language or backward reasoning.
+fn verify(args: &ArgMatches) {
+    let prog_str = args.value_of("program").expect("program is required");
+
+    let program = match parse_dynamic_program(prog_str) {
+        Ok(p) => p,
+        Err(ProgramParseError(msg)) => {
+            eprintln!("Error parsing program or complexity: {}", msg);
+            exit(1)
+        }
+    };
+
+    let mut json = String::new();
+    let read = match args.value_of("certificate") {
+        None | Some("-") => std::io::stdin().read_to_string(&mut json),
+        Some(path) => File::open(path).and_then(|mut file| file.read_to_string(&mut json)),
+    };
+    if let Err(e) = read {
+        eprintln!("Failed to read certificate: {}", e);
+        exit(1)
+    }
+
+    let verified = if let Ok(certificate) = serde_json::from_str::<Certificate>(&json) {
+        certificate.verify(&program)
+    } else if let Ok(certificate) = serde_json::from_str::<closed_language::Certificate>(&json) {
+        certificate.verify(&program)
+    } else if let Ok(proof) = serde_json::from_str::<backward::Proof>(&json) {
+        proof.verify(&program)
+    } else {
+        eprintln!(
+            "Error parsing certificate: it isn't for a recurrence, a closed tape language or \
+             backward reasoning"
+        );
+        exit(1)
+    };
+
+    match verified {
+        Ok(()) => println!("Verified"),
+        Err(e) => {
+            eprintln!("Invalid certificate: {}", e);
+            exit(1)
+        }
+    }
+}
+
+/// Keep, look up and export results in a results database.
+fn db(args: &ArgMatches) {
+    let (name, args) = args.subcommand();
+    let args = args.expect("a db subcommand is required");
+
+    let db = ResultsDb::new(args.value_of("db").expect("db is required"));
+
+    match name {
+        "insert" => db_insert(&db, args),
+        "query" => db_query(&db, args),
+        "holdouts" => db_holdouts(&db, args),
+        _ => unreachable!(),
+    }
+}
+
+fn db_insert(db: &ResultsDb, args: &ArgMatches) {
+    let input: Box<dyn BufRead> = match args.value_of("input") {
+        None | Some("-") => Box::new(BufReader::new(std::io::stdin())),
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Failed to open file: {}", e);
+                exit(1)
+            }
+        },
+    };
+    let parameters = args.value_of("params").map(str::to_string);
+
+    let mut records = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Error reading: {}", e);
+                exit(1)
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Record::parse(&line, parameters.clone()) {
+            Ok(Some(record)) => records.push(record),
+            Ok(None) => (),
+            Err(ResultsError(msg)) => {
+                eprintln!("Error parsing line {}: {}", idx + 1, msg);
+                exit(1)
+            }
+        }
+    }
+
+    match db.insert(records) {
+        Ok(inserted) => eprintln!("Inserted {} results", inserted),
+        Err(ResultsError(msg)) => {
+            eprintln!("Error writing: {}", msg);
+            exit(1)
+        }
+    }
+}
+
+fn db_query(db: &ResultsDb, args: &ArgMatches) {
+    let mut output = parse_output(args)
+        .unwrap_or_else(|| Box::new(BufWriter::with_capacity(1_000, std::io::stdout())));
+
+    for record in db_read(db, db.records(args.value_of("reason"))) {
+        let record = db_read(db, record);
+        let json = serde_json::to_string(&record).expect("Records are always serializable");
+        if let Err(e) = writeln!(output, "{}", json) {
+            eprintln!("Error writing: {}", e);
+            exit(1)
+        }
+    }
+
+    if let Err(e) = output.flush() {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+}
+
+fn db_holdouts(db: &ResultsDb, args: &ArgMatches) {
+    let mut output = parse_output(args)
+        .unwrap_or_else(|| Box::new(BufWriter::with_capacity(1_000, std::io::stdout())));
+
+    if let Err(e) = db_read(db, db.holdouts())
+        .try_for_each(|program| writeln!(output, "{}", program))
+        .and_then(|_| output.flush())
+    {
+        eprintln!("Error writing: {}", e);
+        exit(1)
+    }
+}
+
+/// What was read from the results database, or exit if it couldn't be read.
+fn db_read<T>(db: &ResultsDb, read: Result<T, ResultsError>) -> T {
+    match read {
+        Ok(read) => read,
+        Err(ResultsError(msg)) => {
+            eprintln!("Error reading {}: {}", db.path().display(), msg);
+            exit(1)
         }
     }
 }
 
+fn limit_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("limit")
+        .help("Number of steps to limit the VM to.")
+        .long("limit")
+        .takes_value(true)
+        .number_of_values(1)
+}
+
+fn output_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("output").help("Filename to write output to or - for stdout.")
+}
+
+fn verbose_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("verbose")
+        .short("v")
+        .long("verbose")
+        .takes_value(false)
+        .help("Log each step's state and symbol.")
+}
+
+fn checkpoint_every_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("checkpoint-every")
+        .help("Save a checkpoint every this many steps")
+        .long("checkpoint-every")
+        .takes_value(true)
+        .number_of_values(1)
+}
+
+fn check_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("check-recurrence")
+        .short("c")
+        .long("check")
+        .takes_value(true)
+        .number_of_values(1)
+        .help("Run the recurrence check, taking more time")
+}
+
+fn blank_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("blank")
+        .help("Check blanking beaver starting at this step")
+        .long("blank")
+        .takes_value(true)
+        .number_of_values(1)
+}
+
+fn accelerate_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("accelerate")
+        .short("a")
+        .long("accelerate")
+        .takes_value(false)
+        .help("Sweep across runs of a symbol in one step")
+}
+
+fn deciders_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("deciders")
+        .help(
+            "Deciders to run in order until one is conclusive, each with optional step and time \
+             budgets, eg cycler:1000,recurrence:100000:2.5",
+        )
+        .long("deciders")
+        .takes_value(true)
+        .number_of_values(1)
+}
+
+fn format_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("format")
+        .help("How to write results: text, json or csv")
+        .long("format")
+        .takes_value(true)
+        .number_of_values(1)
+        .possible_values(&["text", "json", "csv"])
+}
+
+fn space_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("space")
+        .long("space")
+        .takes_value(false)
+        .help("Write how far the head went and the steps taken in each state after the summary")
+}
+
+fn db_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("db")
+        .required(true)
+        .help("The results file, created if it doesn't exist")
+}
+
 fn parse_args<'a>() -> clap::ArgMatches<'a> {
     App::new("turing")
         .about("Turing Machine VM")
-        .arg(
-            Arg::with_name("check-recurrence")
-                .short("c")
-                .long("check")
-                .takes_value(true)
-                .number_of_values(1)
-                .help("Run the recurrence check, taking more time"),
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .subcommand(
+            SubCommand::with_name("resume")
+                .about("Carry on a run from a checkpoint")
+                .arg(
+                    Arg::with_name("checkpoint")
+                        .required(true)
+                        .help("The checkpoint file, which is updated as the run goes on"),
+                )
+                .arg(output_arg())
+                .arg(limit_arg().help("Number of steps to limit the VM to, defaults to the run's"))
+                .arg(verbose_arg())
+                .arg(checkpoint_every_arg())
+                .arg(format_arg())
+                .arg(space_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("batch")
+                .about("Run every program in a file, one per line, writing a result line for each")
+                .arg(
+                    Arg::with_name("input")
+                        .help("File to read programs from, - or nothing for stdin"),
+                )
+                .arg(output_arg().help("Filename to write results to or - for stdout, the default"))
+                .arg(limit_arg())
+                .arg(check_arg().conflicts_with("deciders"))
+                .arg(blank_arg().conflicts_with("deciders"))
+                .arg(deciders_arg())
+                .arg(accelerate_arg())
+                .arg(format_arg())
+                .arg(
+                    Arg::with_name("parallel")
+                        .short("p")
+                        .long("parallel")
+                        .takes_value(false)
+                        .help("Run programs in parallel, keeping the input order"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("diagram")
+                .about("Draw the tape at every step as a row of pixels in a PNG or PPM file")
+                .arg(
+                    Arg::with_name("program")
+                        .required(true)
+                        .help("The Turing program. eg 1RB 0LA 1RB 0LH"),
+                )
+                .arg(
+                    Arg::with_name("file")
+                        .required(true)
+                        .help("The image file to write, ending in .png or .ppm"),
+                )
+                .arg(limit_arg().help("Number of steps to draw, defaults to 1000"))
+                .arg(
+                    Arg::with_name("every")
+                        .help("Only draw every this many steps")
+                        .long("every")
+                        .takes_value(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("states")
+                        .long("states")
+                        .takes_value(false)
+                        .help("Color the head by its state"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("dot")
+                .about("Write the program's state graph in Graphviz DOT, eg for dot -Tsvg")
+                .arg(
+                    Arg::with_name("program")
+                        .required(true)
+                        .help("The Turing program. eg 1RB 0LA 1RB 0LH"),
+                )
+                .arg(
+                    output_arg()
+                        .help("Filename to write the graph to or - for stdout, the default"),
+                )
+                .arg(
+                    Arg::with_name("normalize")
+                        .long("normalize")
+                        .takes_value(false)
+                        .help("Normalize the program first"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("ctl")
+                .about("Search for a closed tape language proving the program never halts")
+                .arg(
+                    Arg::with_name("program")
+                        .required(true)
+                        .help("The Turing program. eg 1RB 0LA 1RB 0LH"),
+                )
+                .arg(
+                    output_arg()
+                        .help("Filename to write the certificate to or - for stdout, the default"),
+                )
+                .arg(
+                    Arg::with_name("states")
+                        .long("states")
+                        .takes_value(true)
+                        .number_of_values(1)
+                        .help("The most states each automaton can have, 2 by default"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("backward")
+                .about("Reason backwards from the halting instructions to prove none are reached")
+                .arg(
+                    Arg::with_name("program")
+                        .required(true)
+                        .help("The Turing program. eg 1RB 0LA 1RB 0LH"),
+                )
+                .arg(
+                    output_arg()
+                        .help("Filename to write the proof to or - for stdout, the default"),
+                )
+                .arg(
+                    Arg::with_name("depth")
+                        .long("depth")
+                        .takes_value(true)
+                        .number_of_values(1)
+                        .help("The most steps to reason back, 30 by default"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about(
+                    "Check a certificate that the program never halts, from --certificate, ctl \
+                     or backward",
+                )
+                .arg(
+                    Arg::with_name("program")
+                        .required(true)
+                        .help("The Turing program. eg 1RB 0LA 1RB 0LH"),
+                )
+                .arg(
+                    Arg::with_name("certificate")
+                        .help("The certificate file, - or nothing for stdin"),
+                ),
         )
+        .subcommand(
+            SubCommand::with_name("db")
+                .about("Keep results in a database file, keyed by normalized program")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("insert")
+                        .about("Add the results written by batch --format json")
+                        .arg(db_arg())
+                        .arg(
+                            Arg::with_name("input")
+                                .help("File to read results from, - or nothing for stdin"),
+                        )
+                        .arg(
+                            Arg::with_name("params")
+                                .help("How the programs were run, kept with each result")
+                                .long("params")
+                                .takes_value(true)
+                                .number_of_values(1),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("query")
+                        .about("Write the latest result for each program as JSON lines")
+                        .arg(db_arg())
+                        .arg(
+                            output_arg()
+                                .help("Filename to write results to or - for stdout, the default"),
+                        )
+                        .arg(
+                            Arg::with_name("reason")
+                                .help("Only write results with this reason, eg Halt or XLimit")
+                                .long("reason")
+                                .takes_value(true)
+                                .number_of_values(1),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("holdouts")
+                        .about("Write the programs that reached the limit, one per line")
+                        .arg(db_arg())
+                        .arg(
+                            output_arg()
+                                .help("Filename to write programs to or - for stdout, the default"),
+                        ),
+                ),
+        )
+        .arg(check_arg().conflicts_with("deciders"))
         .arg(
             Arg::with_name("program")
                 .required(true)
                 .help("The Turing program. eg 1RB 0LA 1RB 0LH"),
         )
-        .arg(Arg::with_name("output").help("Filename to write output to or - for stdout."))
+        .arg(output_arg())
+        .arg(limit_arg())
+        .arg(blank_arg().conflicts_with("deciders"))
+        .arg(verbose_arg())
         .arg(
-            Arg::with_name("limit")
-                .help("Number of steps to limit the VM to.")
-                .long("limit")
-                .takes_value(true)
-                .number_of_values(1),
+            Arg::with_name("parallel")
+                .short("p")
+                .long("parallel")
+                .takes_value(false)
+                .conflicts_with("deciders")
+                .help("Run the recurrence check in parallel"),
         )
+        .arg(deciders_arg())
+        .arg(accelerate_arg())
         .arg(
-            Arg::with_name("blank")
-                .help("Check blanking beaver starting at this step")
-                .long("blank")
+            Arg::with_name("block")
+                .help("Simulate a macro machine with blocks of this many cells")
+                .long("block")
                 .takes_value(true)
                 .number_of_values(1),
         )
         .arg(
-            Arg::with_name("verbose")
-                .short("v")
-                .long("verbose")
-                .takes_value(false)
-                .help("Log each step's state and symbol."),
+            Arg::with_name("checkpoint")
+                .help("Save a checkpoint to this file when the limit is reached")
+                .long("checkpoint")
+                .takes_value(true)
+                .number_of_values(1),
         )
+        .arg(checkpoint_every_arg().requires("checkpoint"))
+        .arg(format_arg())
+        .arg(space_arg())
         .arg(
-            Arg::with_name("parallel")
-                .short("p")
-                .long("parallel")
-                .takes_value(false)
-                .help("Run the recurrence check in parallel"),
+            Arg::with_name("certificate")
+                .help("Write a certificate to this file if the run ends in a recurrence")
+                .long("certificate")
+                .takes_value(true)
+                .number_of_values(1),
         )
         .get_matches()
 }
@@ -0,0 +1,302 @@
+use crate::{
+    machine::{Configuration, Halt, HaltReason, Machine},
+    program::Program,
+    types::{State, Symbol},
+};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, collections::BTreeSet};
+
+/// Which kind of recurrence a [`Certificate`] is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Kind {
+    /// Every state the program had been in before the recurrence is in it.
+    Recurr,
+    /// Some states are never reached again, see [`HaltReason::Quasihalt`].
+    Quasihalt,
+}
+
+/// Evidence that a program run from a blank tape is a Lin recurrence, that
+/// can be checked by running it for `start + period` steps.
+///
+/// After `period` steps from `start` the machine is in the same state, with
+/// the head moved by `translation`, and the tape in `window` is the same but
+/// moved by `translation` too. The window covers every cell the head read in
+/// that time, and everything behind the head on the side it moved away from,
+/// so the same steps happen again, moved along, forever.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Certificate {
+    pub kind: Kind,
+    pub start: usize,
+    pub period: usize,
+    pub translation: i64,
+    /// The state at `start`.
+    pub state: String,
+    /// The head position at `start`.
+    pub head: i64,
+    /// The position of the first cell of `window`.
+    pub leftmost: i64,
+    /// The tape compared, one symbol per cell, as it was at `start`.
+    pub window: String,
+}
+
+/// A run of a program from `start` for `period` steps.
+struct Trace<S, Sym> {
+    before: Configuration<S, Sym>,
+    after: Configuration<S, Sym>,
+    /// The leftmost and rightmost positions of the head over the period.
+    span: (i64, i64),
+    kind: Kind,
+}
+
+impl<S: State, Sym: Symbol> Trace<S, Sym> {
+    fn translation(&self) -> i64 {
+        self.after.head - self.before.head
+    }
+
+    /// The cells, from the first up to the last, that must be the same for
+    /// the period to repeat.
+    fn window(&self) -> (i64, i64) {
+        let end = |c: &Configuration<S, Sym>| c.leftmost + c.tape.len() as i64;
+        let translation = self.translation();
+
+        match translation.cmp(&0) {
+            Ordering::Less => (
+                self.before.leftmost.min(self.after.leftmost - translation),
+                self.span.1 + 1,
+            ),
+            Ordering::Greater => (
+                self.span.0,
+                end(&self.before).max(end(&self.after) - translation),
+            ),
+            Ordering::Equal => (self.span.0, self.span.1 + 1),
+        }
+    }
+}
+
+/// Run the program from a blank tape to `start`, then on for `period` steps.
+fn trace<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+    prog: &Program<S, Sym>,
+    start: usize,
+    period: usize,
+) -> Result<Trace<S, Sym>, String> {
+    if period == 0 {
+        return Err("The period must be at least one step".to_string());
+    }
+
+    let mut machine = Machine::new(prog.clone());
+    let step = |machine: &mut Machine<S, Sym>| match machine.step() {
+        Some(step) if step.halt.is_none() => Ok(()),
+        _ => Err(format!("The program halts at step {}", machine.steps())),
+    };
+
+    let mut seen = BTreeSet::new();
+    while machine.steps() < start {
+        seen.insert(machine.state());
+        step(&mut machine)?;
+    }
+    let before = machine.configuration();
+
+    let mut again = BTreeSet::new();
+    let mut span = (before.head, before.head);
+    while machine.steps() < start + period {
+        again.insert(machine.state());
+        step(&mut machine)?;
+        span = (span.0.min(machine.head()), span.1.max(machine.head()));
+    }
+    let after = machine.configuration();
+
+    let kind = if seen.is_subset(&again) {
+        Kind::Recurr
+    } else {
+        Kind::Quasihalt
+    };
+
+    Ok(Trace {
+        before,
+        after,
+        span,
+        kind,
+    })
+}
+
+impl Certificate {
+    /// The certificate for a recurrence found by
+    /// [`Machine::run_until_halt`], worked out again from the program.
+    pub fn new<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+        prog: &Program<S, Sym>,
+        halt: &Halt,
+    ) -> Result<Self, String> {
+        let (kind, period) = match halt.reason {
+            HaltReason::Recurr(period) => (Kind::Recurr, period),
+            HaltReason::Quasihalt(period) => (Kind::Quasihalt, period),
+            _ => return Err("Only recurrences have certificates".to_string()),
+        };
+
+        let trace = trace(prog, halt.steps, period)?;
+        let (first, last) = trace.window();
+
+        let certificate = Self {
+            kind,
+            start: halt.steps,
+            period,
+            translation: trace.translation(),
+            state: trace.before.state.to_string(),
+            head: trace.before.head,
+            leftmost: first,
+            window: (first..last)
+                .map(|pos| trace.before.read(pos).to_string())
+                .collect(),
+        };
+        certificate.verify(prog)?;
+
+        Ok(certificate)
+    }
+
+    /// Check the certificate by running the program, without relying on
+    /// anything about the run that found it.
+    pub fn verify<S: State + Send + Sync, Sym: Symbol + Send + Sync>(
+        &self,
+        prog: &Program<S, Sym>,
+    ) -> Result<(), String> {
+        let trace = trace(prog, self.start, self.period)?;
+        let (before, after) = (&trace.before, &trace.after);
+
+        if before.state.to_string() != self.state || before.head != self.head {
+            return Err(format!(
+                "At step {} the state is {} and the head is at {}",
+                self.start, before.state, before.head
+            ));
+        }
+        if after.state != before.state {
+            return Err(format!(
+                "The state is {} after the period, not {}",
+                after.state, before.state
+            ));
+        }
+        if trace.translation() != self.translation {
+            return Err(format!(
+                "The head moves by {} over the period",
+                trace.translation()
+            ));
+        }
+        if trace.kind != self.kind {
+            return Err(format!("It is a {:?}", trace.kind));
+        }
+
+        let (first, last) = trace.window();
+        let end = self.leftmost + self.window.chars().count() as i64;
+        if self.leftmost > first || end < last {
+            return Err(format!(
+                "The window must cover the cells from {} to {}",
+                first,
+                last - 1
+            ));
+        }
+
+        for (pos, symbol) in (self.leftmost..).zip(self.window.chars()) {
+            if before.read(pos).to_string() != symbol.to_string() {
+                return Err(format!("The cell at {} isn't {} at the start", pos, symbol));
+            }
+            if after.read(pos + self.translation).to_string() != symbol.to_string() {
+                return Err(format!(
+                    "The cell at {} isn't {} after the period",
+                    pos + self.translation,
+                    symbol
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+    use crate::types::{StateT, SymbolT};
+
+    fn prog(prog: &str) -> Program<StateT<'B'>, SymbolT<'1'>> {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => prog,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_certificate() {
+        let prog = prog("1RB 1LB  1LA 0RB");
+        let certificate = Certificate::new(&prog, &Halt::new(7, HaltReason::Recurr(3))).unwrap();
+
+        assert_eq!(
+            certificate,
+            Certificate {
+                kind: Kind::Recurr,
+                start: 7,
+                period: 3,
+                translation: 1,
+                state: "B".to_string(),
+                head: 1,
+                leftmost: 1,
+                window: "1".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_certificate_quasihalt() {
+        let prog = prog("1RB 1LB  1LB 1LA");
+        let certificate = Certificate::new(&prog, &Halt::new(6, HaltReason::Quasihalt(1))).unwrap();
+
+        assert_eq!(certificate.kind, Kind::Quasihalt);
+        assert_eq!(certificate.translation, -1);
+    }
+
+    #[test]
+    fn test_certificate_invalid() {
+        let prog = prog("1RB 1LB  1LA 0RB");
+        let certificate = Certificate::new(&prog, &Halt::new(7, HaltReason::Recurr(3))).unwrap();
+
+        assert!(Certificate::new(&prog, &Halt::new(7, HaltReason::Recurr(2))).is_err());
+        assert!(Certificate::new(&prog, &Halt::new(7, HaltReason::Quasihalt(3))).is_err());
+        assert!(Certificate::new(&prog, &Halt::new(7, HaltReason::XLimit)).is_err());
+
+        let shorter = Certificate {
+            leftmost: 2,
+            window: String::new(),
+            ..certificate.clone()
+        };
+        assert!(shorter.verify(&prog).is_err());
+
+        let wrong = Certificate {
+            window: "0".to_string(),
+            ..certificate.clone()
+        };
+        assert!(wrong.verify(&prog).is_err());
+
+        let later = Certificate {
+            start: 8,
+            ..certificate
+        };
+        assert!(later.verify(&prog).is_err());
+    }
+
+    #[test]
+    fn test_certificate_halts() {
+        let certificate = Certificate {
+            kind: Kind::Recurr,
+            start: 0,
+            period: 10,
+            translation: 0,
+            state: "A".to_string(),
+            head: 0,
+            leftmost: 0,
+            window: "0".to_string(),
+        };
+
+        assert_eq!(
+            certificate.verify(&prog("1RB 1LB  1LA 1RH")),
+            Err("The program halts at step 6".to_string())
+        );
+    }
+}
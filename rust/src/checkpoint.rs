@@ -0,0 +1,289 @@
+use crate::{
+    machine::{Beeps, Machine, Recurrence, Snapshots, Space},
+    program::Program,
+    tape::Tape,
+    types::{Direction, State, Symbol},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct CheckpointError(pub String);
+
+/// The cells of a tape, as runs of a symbol starting at `leftmost`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TapeCheckpoint {
+    pub leftmost: i64,
+    pub runs: Vec<(String, u64)>,
+}
+
+/// A tape the recurrence check saved, and the step it was saved on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotCheckpoint {
+    pub state: String,
+    pub symbol: String,
+    pub step: usize,
+    pub head: i64,
+    pub tape: TapeCheckpoint,
+    pub beeps: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecurrenceCheckpoint {
+    /// The head position at every step from `first`.
+    pub deviations: Vec<i64>,
+    pub first: usize,
+    pub snapshots: Vec<SnapshotCheckpoint>,
+}
+
+/// See [`Space`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpaceCheckpoint {
+    pub leftmost: i64,
+    pub rightmost: i64,
+    pub visits: BTreeMap<String, usize>,
+}
+
+/// How a run was started, so that it can be carried on the same way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunOptions {
+    pub limit: usize,
+    pub check_recurrence: Option<usize>,
+    pub check_blank: Option<usize>,
+    pub parallel: bool,
+    pub accelerate: bool,
+}
+
+/// Everything needed to carry on running a [`Machine`] later, possibly in
+/// another process. States and symbols are stored as they are written in
+/// programs, so a checkpoint can be read back without knowing the program's
+/// complexity up front.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub program: String,
+    pub state: String,
+    pub head: i64,
+    pub tape: TapeCheckpoint,
+    pub steps: usize,
+    pub marks: usize,
+    pub beeps: BTreeMap<String, usize>,
+    /// The recurrence check's snapshots, if it was running and they were
+    /// asked for.
+    pub recurrence: Option<RecurrenceCheckpoint>,
+    pub space: SpaceCheckpoint,
+    /// Set by whoever is running the machine, the machine doesn't know.
+    pub options: Option<RunOptions>,
+}
+
+impl Checkpoint {
+    pub fn with_options(self, options: RunOptions) -> Self {
+        Self {
+            options: Some(options),
+            ..self
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Checkpoints are always serializable")
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, CheckpointError> {
+        serde_json::from_str(s).map_err(|e| CheckpointError(e.to_string()))
+    }
+
+    /// Write the checkpoint to `path`. It is written to a temporary file
+    /// first and synced to disk before it replaces the old one, so an
+    /// interrupted save leaves the previous checkpoint intact.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        let file = File::create(&tmp).map_err(|e| CheckpointError(e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, self).map_err(|e| CheckpointError(e.to_string()))?;
+        writer
+            .flush()
+            .and_then(|_| writer.get_ref().sync_all())
+            .map_err(|e| CheckpointError(e.to_string()))?;
+
+        fs::rename(&tmp, path).map_err(|e| CheckpointError(e.to_string()))?;
+        sync_dir(path)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, CheckpointError> {
+        let file = File::open(path).map_err(|e| CheckpointError(e.to_string()))?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| CheckpointError(e.to_string()))
+    }
+}
+
+/// Sync the directory `path` is in, so a rename into it is on disk. Only
+/// Unix can open a directory to sync it.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> Result<(), CheckpointError> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| CheckpointError(e.to_string()))
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> Result<(), CheckpointError> {
+    Ok(())
+}
+
+fn parse_state<S: State>(s: &str) -> Result<S, CheckpointError> {
+    S::states()
+        .into_iter()
+        .chain(std::iter::once(S::halt()))
+        .find(|state| state.to_string() == s)
+        .ok_or_else(|| CheckpointError(format!("Unknown state {}", s)))
+}
+
+fn parse_symbol<Sym: Symbol>(s: &str) -> Result<Sym, CheckpointError> {
+    Sym::symbols()
+        .into_iter()
+        .find(|symbol| symbol.to_string() == s)
+        .ok_or_else(|| CheckpointError(format!("Unknown symbol {}", s)))
+}
+
+fn save_beeps<S: State>(beeps: &Beeps<S>) -> BTreeMap<String, usize> {
+    beeps.iter().map(|(s, n)| (s.to_string(), *n)).collect()
+}
+
+fn load_beeps<S: State>(beeps: &BTreeMap<String, usize>) -> Result<Beeps<S>, CheckpointError> {
+    beeps
+        .iter()
+        .map(|(s, n)| Ok((parse_state(s)?, *n)))
+        .collect()
+}
+
+fn save_tape<Sym: Symbol, T: Tape<Sym>>(tape: &T) -> TapeCheckpoint {
+    let mut runs: Vec<(Sym, u64)> = vec![];
+    for symbol in tape.iter() {
+        match runs.last_mut() {
+            Some((s, n)) if *s == symbol => *n += 1,
+            _ => runs.push((symbol, 1)),
+        }
+    }
+    TapeCheckpoint {
+        leftmost: tape.leftmost(),
+        runs: runs.into_iter().map(|(s, n)| (s.to_string(), n)).collect(),
+    }
+}
+
+fn load_tape<Sym: Symbol, T: Tape<Sym>>(checkpoint: &TapeCheckpoint) -> Result<T, CheckpointError> {
+    let mut tape = T::default();
+    let mut pos = checkpoint.leftmost;
+    for (s, n) in &checkpoint.runs {
+        tape.fill(pos, Direction::Right, *n, parse_symbol(s)?);
+        pos += *n as i64;
+    }
+    Ok(tape)
+}
+
+impl<
+        S: State + Send + Sync + ToString,
+        Sym: Symbol + Send + Sync + ToString,
+        T: Tape<Sym> + Send + Sync,
+    > Machine<S, Sym, T>
+{
+    /// Save the machine, leaving out the recurrence check's snapshots unless
+    /// `snapshots` is set. Without them, a resumed recurrence check only
+    /// finds recurrences that start after the checkpoint.
+    pub fn checkpoint(&self, snapshots: bool) -> Checkpoint {
+        let recurrence = self.recurrence().filter(|_| snapshots).map(|r| {
+            let snapshots = r
+                .snapshots
+                .iter()
+                .flat_map(|((state, symbol), snaps)| {
+                    snaps
+                        .iter()
+                        .map(move |(step, head, tape, beeps)| SnapshotCheckpoint {
+                            state: state.to_string(),
+                            symbol: symbol.to_string(),
+                            step: *step,
+                            head: *head,
+                            tape: save_tape(tape),
+                            beeps: save_beeps(beeps),
+                        })
+                })
+                .collect();
+            RecurrenceCheckpoint {
+                deviations: r.deviations.clone(),
+                first: r.first,
+                snapshots,
+            }
+        });
+
+        Checkpoint {
+            program: self.program().to_string(),
+            state: self.state().to_string(),
+            head: self.head(),
+            tape: save_tape(self.tape()),
+            steps: self.steps(),
+            marks: self.mark_count(),
+            beeps: save_beeps(self.beeps()),
+            recurrence,
+            space: SpaceCheckpoint {
+                leftmost: self.space().leftmost,
+                rightmost: self.space().rightmost,
+                visits: save_beeps(&self.space().visits),
+            },
+            options: None,
+        }
+    }
+
+    /// Restore a machine for `prog` from a checkpoint of it.
+    pub fn restore(
+        prog: Program<S, Sym>,
+        checkpoint: &Checkpoint,
+    ) -> Result<Self, CheckpointError> {
+        let recurrence = match &checkpoint.recurrence {
+            Some(r) => {
+                let mut snapshots: Snapshots<S, Sym, T> = BTreeMap::new();
+                for snap in &r.snapshots {
+                    let action = (parse_state(&snap.state)?, parse_symbol(&snap.symbol)?);
+                    snapshots.entry(action).or_default().push((
+                        snap.step,
+                        snap.head,
+                        load_tape(&snap.tape)?,
+                        load_beeps(&snap.beeps)?,
+                    ));
+                }
+                Some(Recurrence {
+                    snapshots,
+                    deviations: r.deviations.clone(),
+                    first: r.first,
+                })
+            }
+            None => None,
+        };
+
+        let space = Space {
+            leftmost: checkpoint.space.leftmost,
+            rightmost: checkpoint.space.rightmost,
+            visits: load_beeps(&checkpoint.space.visits)?,
+        };
+
+        Ok(Self::from_parts(
+            prog,
+            parse_state(&checkpoint.state)?,
+            checkpoint.head,
+            load_tape(&checkpoint.tape)?,
+            checkpoint.steps,
+            checkpoint.marks,
+            load_beeps(&checkpoint.beeps)?,
+            recurrence,
+            space,
+        ))
+    }
+}
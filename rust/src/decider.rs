@@ -0,0 +1,7 @@
+//! Deciders that prove a machine never halts, without running it forever.
+
+pub mod backward;
+pub mod bouncer;
+pub mod closed_language;
+pub mod cycler;
+pub mod translated_cycler;
@@ -0,0 +1,286 @@
+use crate::{
+    decider::closed_language::{table, Table},
+    program::Program,
+    types::{Direction, State, Symbol},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Instant};
+
+/// How many configurations are traced back between looks at the clock.
+const DEADLINE_CONFIGURATIONS: usize = 1 << 12;
+
+/// What reasoning backwards from the halting instructions found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backward {
+    /// No configuration could have led to a halt, so the program never halts
+    /// from a blank tape.
+    NonHalting(Proof),
+    /// There may be a way to halt, or it was too far back to tell in time.
+    Unknown,
+}
+
+/// A configuration of which only the state and some cells are known, placed
+/// relative to where the head was when the program halted. States and
+/// symbols are numbered in order from `0`, as in a closed language
+/// [`Certificate`](crate::decider::closed_language::Certificate).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Partial {
+    pub state: usize,
+    pub head: i64,
+    /// The symbols on the cells that are known.
+    pub tape: BTreeMap<i64, usize>,
+}
+
+/// How a [`Backward::NonHalting`] verdict was reached, which can be checked
+/// a level at a time with [`Proof::verify`].
+///
+/// The first level has the configurations the program halts in, and each
+/// level after it every configuration that leads to one in the level before
+/// in a step, in order. None of the configurations could be the blank tape
+/// the program starts on, and none lead to those in the last level.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proof {
+    pub levels: Vec<Vec<Partial>>,
+}
+
+impl Partial {
+    fn is_initial(&self) -> bool {
+        self.state == 0 && self.tape.values().all(|&symbol| symbol == 0)
+    }
+}
+
+impl Proof {
+    /// The most steps any configuration could be traced back before there
+    /// was no way it could have been reached.
+    pub fn depth(&self) -> usize {
+        self.levels.len().saturating_sub(1)
+    }
+
+    /// The number of configurations that were traced back, including the
+    /// halting ones.
+    pub fn configurations(&self) -> usize {
+        self.levels.iter().map(Vec::len).sum()
+    }
+
+    /// Check that the proof shows the program never halts: the first level
+    /// has every configuration it halts in, each level after it has exactly
+    /// the configurations that lead to the level before, and there are none
+    /// leading to the last level or that are the blank tape.
+    pub fn verify<S: State, Sym: Symbol>(&self, prog: &Program<S, Sym>) -> Result<(), String> {
+        let table = table(prog);
+
+        let mut expected = halting(&table);
+        for (depth, level) in self.levels.iter().enumerate() {
+            expected.sort();
+            if *level != expected {
+                return Err(format!(
+                    "Level {} doesn't have the configurations that lead to the one before",
+                    depth
+                ));
+            }
+            if let Some(partial) = level.iter().find(|partial| partial.is_initial()) {
+                return Err(format!("{:?} could be the blank tape", partial));
+            }
+            expected = level
+                .iter()
+                .flat_map(|partial| predecessors(&table, partial))
+                .collect();
+        }
+
+        match expected.first() {
+            Some(partial) => Err(format!("{:?} leads to the last level", partial)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The configurations the program halts in, where only the state and the
+/// symbol under the head are known.
+fn halting(table: &Table) -> Vec<Partial> {
+    table
+        .iter()
+        .enumerate()
+        .flat_map(|(state, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, instruction)| instruction.is_none())
+                .map(move |(symbol, _)| Partial {
+                    state,
+                    head: 0,
+                    tape: std::iter::once((0, symbol)).collect(),
+                })
+        })
+        .collect()
+}
+
+/// The configurations that lead to `partial` in a step. An instruction
+/// can't have led to it if it would have written something other than what
+/// is known to be on the tape.
+fn predecessors(table: &Table, partial: &Partial) -> Vec<Partial> {
+    let mut predecessors = vec![];
+
+    for (state, row) in table.iter().enumerate() {
+        for (read, instruction) in row.iter().enumerate() {
+            let (write, direction) = match instruction {
+                Some((next, write, direction)) if *next == partial.state => (*write, *direction),
+                _ => continue,
+            };
+
+            let head = match direction {
+                Direction::Left => partial.head + 1,
+                Direction::Right => partial.head - 1,
+            };
+
+            match partial.tape.get(&head) {
+                Some(&symbol) if symbol != write => continue,
+                _ => (),
+            }
+
+            let mut tape = partial.tape.clone();
+            tape.insert(head, read);
+
+            predecessors.push(Partial { state, head, tape });
+        }
+    }
+
+    predecessors
+}
+
+/// Try to prove that a program never halts without running it, by working
+/// backwards from every instruction that halts or is undefined.
+///
+/// Each of those gives a configuration of which only the state and the
+/// symbol under the head are known. For each instruction that could have
+/// led to a configuration, the one before it is worked out. If every way
+/// back runs out within `depth` steps, and none of them could be the blank
+/// tape the program starts on, the program never halts. Otherwise the result
+/// is unknown, as it is if `deadline` passes first.
+///
+/// The ways back are followed one at a time, so the search gives up as soon
+/// as one of them goes too far, without working out the others.
+pub fn backward_reasoning<S: State, Sym: Symbol>(
+    prog: &Program<S, Sym>,
+    depth: usize,
+    deadline: Option<Instant>,
+) -> Backward {
+    let table = table(prog);
+    let mut traced = 0;
+
+    let mut levels: Vec<Vec<Partial>> = vec![];
+    let mut partials = halting(&table)
+        .into_iter()
+        .map(|partial| (partial, 0))
+        .collect::<Vec<_>>();
+
+    while let Some((partial, level)) = partials.pop() {
+        if partial.is_initial() || level >= depth {
+            return Backward::Unknown;
+        }
+
+        traced += 1;
+        if traced % DEADLINE_CONFIGURATIONS == 0 && deadline.is_some_and(|d| Instant::now() >= d) {
+            return Backward::Unknown;
+        }
+
+        partials.extend(
+            predecessors(&table, &partial)
+                .into_iter()
+                .map(|partial| (partial, level + 1)),
+        );
+
+        if levels.len() == level {
+            levels.push(vec![]);
+        }
+        levels[level].push(partial);
+    }
+
+    for level in &mut levels {
+        level.sort();
+    }
+
+    Backward::NonHalting(Proof { levels })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    fn backward(prog: &str, depth: usize) -> Backward {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => {
+                let backward = backward_reasoning(&prog, depth, None);
+                if let Backward::NonHalting(proof) = &backward {
+                    assert_eq!(proof.verify(&prog), Ok(()));
+                }
+                backward
+            }
+            ProgramT::ThreeTwo(prog) => backward_reasoning(&prog, depth, None),
+            _ => unreachable!(),
+        }
+    }
+
+    fn proof(prog: &str, depth: usize) -> Proof {
+        match backward(prog, depth) {
+            Backward::NonHalting(proof) => proof,
+            Backward::Unknown => panic!("{} wasn't proved", prog),
+        }
+    }
+
+    #[test]
+    fn test_backward_reasoning() {
+        // Nothing halts.
+        let nothing = proof("1RB 1LB  1LA 0RB", 10);
+        assert_eq!(nothing, Proof { levels: vec![] });
+        assert_eq!((nothing.depth(), nothing.configurations()), (0, 0));
+
+        // Never writes the 1 it would halt on.
+        let never = proof("0RB 1RH  0LA 0RB", 10);
+        assert_eq!((never.depth(), never.configurations()), (1, 2));
+        assert_eq!(
+            never.levels[1],
+            vec![Partial {
+                state: 1,
+                head: 1,
+                tape: BTreeMap::from([(0, 1), (1, 0)])
+            }]
+        );
+    }
+
+    #[test]
+    fn test_backward_reasoning_invalid() {
+        let prog = parse("0RB 1RH  0LA 0RB");
+        let proof = proof("0RB 1RH  0LA 0RB", 10);
+
+        let mut short = proof.clone();
+        short.levels.pop();
+        assert!(short.verify(&prog).is_err());
+
+        let mut wrong = proof.clone();
+        wrong.levels[1][0].head = 2;
+        assert!(wrong.verify(&prog).is_err());
+
+        assert!(Proof { levels: vec![] }.verify(&prog).is_err());
+        assert!(proof.verify(&parse("0RB 1RH  0LA 1RB")).is_err());
+    }
+
+    #[test]
+    fn test_backward_reasoning_unknown() {
+        // These halt.
+        assert_eq!(backward("1RB 1LB  1LA 1RH", 100), Backward::Unknown);
+        assert_eq!(
+            backward("1RB 1RH  1LB 0RC  1LC 1LA", 100),
+            Backward::Unknown
+        );
+        // These never halt, but there are always more ways they could have.
+        assert_eq!(backward("1RB 1RH  0RA 0RA", 100), Backward::Unknown);
+        assert_eq!(backward("0RB 1RH  0LA 0LB", 100), Backward::Unknown);
+    }
+
+    fn parse(prog: &str) -> Program<crate::types::StateT<'B'>, crate::types::SymbolT<'1'>> {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => prog,
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,516 @@
+use crate::{
+    machine::{Configuration, Halt, HaltReason, Machine},
+    program::Program,
+    tape::Tape,
+    types::{Direction, State, Symbol},
+};
+
+/// How many steps the head may take inside one copy of a repeated word
+/// before giving up on it getting through.
+const SHIFT_LIMIT: usize = 1_000;
+
+/// How many steps of the inductive step to take before giving up.
+const SYMBOLIC_LIMIT: usize = 100_000;
+
+/// The longest tape compared when guessing a formula.
+const TAPE_LIMIT: usize = 1_000;
+
+type Word<Sym> = Vec<Sym>;
+
+/// Words inserted into a tape, with how many of its cells come before each.
+type Insertions<Sym> = Vec<(usize, Word<Sym>)>;
+
+/// A run of cells in a [`Formula`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item<Sym> {
+    Cell(Sym),
+    /// A word repeated `n + extra` times, for any `n`.
+    Repeat(Vec<Sym>, usize),
+}
+
+/// A family of configurations, one for each `n`, with blank tape either
+/// side. The head is on a cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Formula<S, Sym> {
+    items: Vec<Item<Sym>>,
+    head: usize,
+    state: S,
+}
+
+/// Run the machine for up to `limit` steps, watching for a bouncer: a
+/// machine that sweeps back and forth across the tape, which grows by the
+/// same words each time.
+///
+/// If one is found the machine is stopped with [`HaltReason::Bouncer`],
+/// with the step the growth was first seen on as the halt step. Otherwise
+/// it halts as it would have done or stops with [`HaltReason::XLimit`] at
+/// `limit`.
+///
+/// Whenever the head reaches a new rightmost (or leftmost) cell, the tape
+/// is compared to earlier times it did so in the same state. If it has grown
+/// by the same words inserted in the same places each time, it is written as
+/// a formula with those words repeated `n` times. Then the formula is run
+/// symbolically, crossing repeated words in one go, and if it reaches the
+/// same formula with `n + 1` the machine never halts.
+pub fn check_bouncer<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    machine: &mut Machine<S, Sym, T>,
+    limit: usize,
+) {
+    if machine.halt().is_some() {
+        return;
+    }
+
+    let mut rights: Vec<Configuration<S, Sym>> = vec![];
+    let mut lefts: Vec<Configuration<S, Sym>> = vec![];
+    let (mut rightmost, mut leftmost) = (machine.head(), machine.head());
+
+    while machine.steps() < limit {
+        if machine.step().and_then(|step| step.halt).is_some() {
+            return;
+        }
+
+        let head = machine.head();
+        let (records, direction) = if head > rightmost {
+            rightmost = head;
+            (&mut rights, Direction::Right)
+        } else if head < leftmost {
+            leftmost = head;
+            (&mut lefts, Direction::Left)
+        } else {
+            continue;
+        };
+
+        records.push(machine.configuration());
+
+        if let Some(halt) = find_bouncer(machine.program(), records, direction) {
+            machine.set_halt(halt);
+            return;
+        }
+    }
+
+    machine.set_halt(Halt::new(limit, HaltReason::XLimit));
+}
+
+/// Look for a bouncer ending with the latest record, trying the records in
+/// the same state one, two or three apart.
+fn find_bouncer<S: State, Sym: Symbol>(
+    prog: &Program<S, Sym>,
+    records: &[Configuration<S, Sym>],
+    direction: Direction,
+) -> Option<Halt> {
+    let last = records.last()?;
+    let same = records
+        .iter()
+        .rev()
+        .filter(|c| c.state == last.state)
+        .take(7)
+        .collect::<Vec<_>>();
+
+    (1..=3).find_map(|apart| {
+        let (first, second) = (same.get(2 * apart)?, same.get(apart)?);
+        let (formula, insertions) = guess(first, second, last, direction)?;
+
+        if !prove(prog, &formula) {
+            return None;
+        }
+
+        let reason = HaltReason::Bouncer(
+            second.step - first.step,
+            insertions
+                .iter()
+                .map(|(_, word)| word.iter().map(ToString::to_string).collect())
+                .collect(),
+        );
+        Some(Halt::new(first.step, reason))
+    })
+}
+
+/// The tape without the cell the head is on, which is at the end it is
+/// moving towards.
+fn body<S, Sym: Copy>(configuration: &Configuration<S, Sym>, direction: Direction) -> &[Sym] {
+    match direction {
+        Direction::Right => &configuration.tape[..configuration.tape.len() - 1],
+        Direction::Left => &configuration.tape[1..],
+    }
+}
+
+/// A formula the three configurations are the first three of, with where
+/// the tape grows, if it grew the same way both times.
+fn guess<S: State, Sym: Symbol>(
+    first: &Configuration<S, Sym>,
+    second: &Configuration<S, Sym>,
+    third: &Configuration<S, Sym>,
+    direction: Direction,
+) -> Option<(Formula<S, Sym>, Insertions<Sym>)> {
+    let (a, b, c) = (
+        body(first, direction),
+        body(second, direction),
+        body(third, direction),
+    );
+    if c.len() > TAPE_LIMIT || b.len() <= a.len() || c.len() - b.len() != b.len() - a.len() {
+        return None;
+    }
+
+    let insertions = vec![insertions(a, b), insertions_late(a, b)]
+        .into_iter()
+        .flatten()
+        .find(|insertions| expand(a, insertions, 2) == c)?;
+
+    let mut items = vec![];
+    let mut cells = a.iter();
+    let mut at = 0;
+    for (pos, word) in &insertions {
+        items.extend(cells.by_ref().take(pos - at).map(|&s| Item::Cell(s)));
+        items.push(Item::Repeat(word.clone(), 0));
+        at = *pos;
+    }
+    items.extend(cells.map(|&s| Item::Cell(s)));
+
+    let head_symbol = match direction {
+        Direction::Right => first.tape[first.tape.len() - 1],
+        Direction::Left => first.tape[0],
+    };
+    let head = match direction {
+        Direction::Right => {
+            items.push(Item::Cell(head_symbol));
+            items.len() - 1
+        }
+        Direction::Left => {
+            items.insert(0, Item::Cell(head_symbol));
+            0
+        }
+    };
+
+    let mut formula = Formula {
+        items,
+        head,
+        state: first.state,
+    };
+    formula.normalize();
+
+    Some((formula, insertions))
+}
+
+/// The words `b` has that `a` doesn't, with how many cells of `a` come
+/// before each, if `b` is `a` with words inserted. Cells of `a` are matched
+/// as early in `b` as they can be.
+fn insertions<Sym: Symbol>(a: &[Sym], b: &[Sym]) -> Option<Insertions<Sym>> {
+    let mut insertions: Insertions<Sym> = vec![];
+    let mut i = 0;
+    for &symbol in b {
+        if a.get(i) == Some(&symbol) {
+            i += 1;
+            continue;
+        }
+        match insertions.last_mut() {
+            Some((pos, word)) if *pos == i => word.push(symbol),
+            _ => insertions.push((i, vec![symbol])),
+        }
+    }
+    (i == a.len()).then_some(insertions)
+}
+
+/// Like [`insertions`], but matching cells of `a` as late as they can be.
+fn insertions_late<Sym: Symbol>(a: &[Sym], b: &[Sym]) -> Option<Insertions<Sym>> {
+    let reversed = |t: &[Sym]| t.iter().rev().copied().collect::<Vec<_>>();
+    let insertions = insertions(&reversed(a), &reversed(b))?;
+
+    Some(
+        insertions
+            .into_iter()
+            .rev()
+            .map(|(pos, word)| (a.len() - pos, reversed(&word)))
+            .collect(),
+    )
+}
+
+/// `a` with each word inserted `n` times.
+fn expand<Sym: Symbol>(a: &[Sym], insertions: &[(usize, Word<Sym>)], n: usize) -> Vec<Sym> {
+    let mut tape = vec![];
+    let mut at = 0;
+    for (pos, word) in insertions {
+        tape.extend_from_slice(&a[at..*pos]);
+        for _ in 0..n {
+            tape.extend_from_slice(word);
+        }
+        at = *pos;
+    }
+    tape.extend_from_slice(&a[at..]);
+    tape
+}
+
+/// Whether running the formula symbolically reaches the same formula with
+/// one more of each repeated word.
+fn prove<S: State, Sym: Symbol>(prog: &Program<S, Sym>, formula: &Formula<S, Sym>) -> bool {
+    let mut target = formula.clone();
+    for item in &mut target.items {
+        if let Item::Repeat(_, extra) = item {
+            *extra += 1;
+        }
+    }
+
+    let at_edge = |f: &Formula<S, Sym>| f.head == 0 || f.head == f.items.len() - 1;
+
+    let mut current = formula.clone();
+    for _ in 0..SYMBOLIC_LIMIT {
+        if current.step(prog).is_none() || current.items.len() > TAPE_LIMIT {
+            return false;
+        }
+        if current.state == target.state && at_edge(&current) {
+            let mut normalized = current.clone();
+            normalized.normalize();
+            if normalized == target {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+impl<S: State, Sym: Symbol> Formula<S, Sym> {
+    /// Take one step, crossing any repeated words the head moves onto, or
+    /// return `None` if the formula might halt or the words can't be
+    /// crossed.
+    fn step(&mut self, prog: &Program<S, Sym>) -> Option<()> {
+        let read = match self.items[self.head] {
+            Item::Cell(symbol) => symbol,
+            Item::Repeat(..) => unreachable!("The head is always on a cell"),
+        };
+        let &(state, write, direction) = prog.instruction(self.state, read)?;
+        if state == S::halt() {
+            return None;
+        }
+
+        self.items[self.head] = Item::Cell(write);
+        self.state = state;
+        self.move_head(direction);
+
+        while let Item::Repeat(word, extra) = &self.items[self.head] {
+            let (word, extra) = (word.clone(), *extra);
+
+            if let Some(shifted) = shift(prog, &word, self.state, direction) {
+                self.items[self.head] = Item::Repeat(shifted, extra);
+                self.move_head(direction);
+            } else if extra > 0 {
+                // Take one copy out, there is always at least one.
+                let cells = word.iter().map(|&s| Item::Cell(s));
+                let rest = Item::Repeat(word.clone(), extra - 1);
+                let replacement = match direction {
+                    Direction::Right => cells.chain(std::iter::once(rest)).collect::<Vec<_>>(),
+                    Direction::Left => std::iter::once(rest).chain(cells).collect(),
+                };
+                let idx = self.head;
+                self.items.splice(idx..=idx, replacement);
+                if direction == Direction::Left {
+                    self.head += word.len();
+                }
+            } else {
+                return None;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Move the head onto the next item, adding a blank cell at the ends.
+    fn move_head(&mut self, direction: Direction) {
+        match direction {
+            Direction::Left if self.head == 0 => self.items.insert(0, Item::Cell(Sym::zero())),
+            Direction::Left => self.head -= 1,
+            Direction::Right => {
+                self.head += 1;
+                if self.head == self.items.len() {
+                    self.items.push(Item::Cell(Sym::zero()));
+                }
+            }
+        }
+    }
+
+    /// Write the formula the same way as any other for the same tapes, as
+    /// far as possible: cells are moved from the left of repeated words to
+    /// the right, where they are folded into the words if they spell one,
+    /// and blank cells at the ends are dropped. The head's cell stays put.
+    fn normalize(&mut self) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for idx in 0..self.items.len() {
+                let (word, extra) = match &self.items[idx] {
+                    Item::Repeat(word, extra) => (word.clone(), *extra),
+                    Item::Cell(_) => continue,
+                };
+
+                // x (w x)^n is the same as (x w)^n x.
+                if idx > 0 && self.head != idx - 1 {
+                    if let Item::Cell(symbol) = self.items[idx - 1] {
+                        if word.last() == Some(&symbol) {
+                            let mut rotated = word.clone();
+                            rotated.rotate_right(1);
+                            self.items[idx - 1] = Item::Repeat(rotated, extra);
+                            self.items[idx] = Item::Cell(symbol);
+                            changed = true;
+                            break;
+                        }
+                    }
+                }
+
+                let after = idx + 1..idx + 1 + word.len();
+                let spelled = after.end <= self.items.len()
+                    && !after.contains(&self.head)
+                    && self.items[after.clone()]
+                        .iter()
+                        .zip(&word)
+                        .all(|(item, symbol)| *item == Item::Cell(*symbol));
+                if spelled {
+                    self.items.drain(after);
+                    if self.head > idx {
+                        self.head -= word.len();
+                    }
+                    self.items[idx] = Item::Repeat(word, extra + 1);
+                    changed = true;
+                    break;
+                }
+            }
+        }
+
+        while self.head > 0 && self.items[0] == Item::Cell(Sym::zero()) {
+            self.items.remove(0);
+            self.head -= 1;
+        }
+        while self.head < self.items.len() - 1
+            && self.items.last() == Some(&Item::Cell(Sym::zero()))
+        {
+            self.items.pop();
+        }
+    }
+}
+
+/// What one copy of `word` becomes if the head goes in at one end in
+/// `state`, moving in `direction`, and comes out of the other end in the
+/// same state, without ever leaving the word before then.
+fn shift<S: State, Sym: Symbol>(
+    prog: &Program<S, Sym>,
+    word: &[Sym],
+    state: S,
+    direction: Direction,
+) -> Option<Vec<Sym>> {
+    let mut word = word.to_vec();
+    let mut pos = match direction {
+        Direction::Right => 0,
+        Direction::Left => word.len() as i64 - 1,
+    };
+    let mut current = state;
+
+    for _ in 0..SHIFT_LIMIT {
+        let &(next, write, move_to) = prog.instruction(current, word[pos as usize])?;
+        if next == S::halt() {
+            return None;
+        }
+        word[pos as usize] = write;
+        current = next;
+        pos += match move_to {
+            Direction::Left => -1,
+            Direction::Right => 1,
+        };
+
+        let out = match direction {
+            Direction::Right if pos == word.len() as i64 => true,
+            Direction::Left if pos == -1 => true,
+            _ if pos < 0 || pos >= word.len() as i64 => return None,
+            _ => false,
+        };
+        if out {
+            return (current == state).then_some(word);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    fn check(prog: &str, limit: usize) -> Halt {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => {
+                let mut machine = Machine::new(prog);
+                check_bouncer(&mut machine, limit);
+                machine.halt().cloned().unwrap()
+            }
+            ProgramT::FourTwo(prog) => {
+                let mut machine = Machine::new(prog);
+                check_bouncer(&mut machine, limit);
+                machine.halt().cloned().unwrap()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_bouncer() {
+        let halt = check("1RB 1LA  1LA 1RB", 1000);
+
+        assert!(
+            matches!(halt.reason, HaltReason::Bouncer(_, ref words) if words == &["11"]),
+            "{:?}",
+            halt
+        );
+    }
+
+    #[test]
+    fn test_bouncer_words() {
+        assert_eq!(
+            check("0RB 1RC  1LA 1RB  1LB 1LC  ... ...", 1000),
+            Halt::new(4, HaltReason::Bouncer(11, vec!["111".to_string()]))
+        );
+        assert_eq!(
+            check("0RB 0LC  1LA 1RB  1LD 1LC  1LB ...", 1000),
+            Halt::new(
+                7,
+                HaltReason::Bouncer(11, vec!["11".to_string(), "1".to_string()])
+            )
+        );
+    }
+
+    #[test]
+    fn test_not_bouncer() {
+        assert_eq!(
+            check("1RB 1LB  1LA 1RH", 100),
+            Halt::new(6, HaltReason::Halt)
+        );
+        // Cycles without moving along the tape.
+        assert_eq!(
+            check("1RB 1RB  1LA 1LA", 1000),
+            Halt::new(1000, HaltReason::XLimit)
+        );
+    }
+
+    #[test]
+    fn test_insertions() {
+        let sym = |s: &str| {
+            s.chars()
+                .map(crate::types::SymbolT::<'1'>)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            insertions(&sym("101"), &sym("10101")),
+            Some(vec![(3, sym("01"))])
+        );
+        assert_eq!(
+            insertions_late(&sym("101"), &sym("10101")),
+            Some(vec![(0, sym("10"))])
+        );
+        assert_eq!(insertions(&sym("11"), &sym("10")), None);
+        assert_eq!(
+            expand(&sym("101"), &[(1, sym("0")), (3, sym("11"))], 2),
+            sym("100011111")
+        );
+    }
+}
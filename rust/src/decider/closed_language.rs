@@ -0,0 +1,352 @@
+use crate::{
+    program::Program,
+    types::{Direction, State, Symbol},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, time::Instant};
+
+/// A deterministic automaton reading the tape from one end towards the head.
+/// `transitions[q][i]` is the state after reading the `i`th symbol of the
+/// program in state `q`. It starts in state `0`, which reading a blank keeps
+/// it in, so the blanks at the ends of the tape make no difference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dfa {
+    pub transitions: Vec<Vec<usize>>,
+}
+
+/// A regular language of tapes closed under the program's transitions, that
+/// contains the blank tape and none the program halts on. Its existence
+/// proves the program never halts.
+///
+/// A configuration is in the language when the state `left` is in after
+/// reading the tape left of the head from the left, the program's state and
+/// symbol under the head, and the state `right` is in after reading the tape
+/// right of the head from the right, are one of the `accepted` tuples.
+/// Program states and symbols are numbered in order from `0`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Certificate {
+    pub left: Dfa,
+    pub right: Dfa,
+    pub accepted: BTreeSet<(usize, usize, usize, usize)>,
+}
+
+impl Dfa {
+    fn states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// For each state and symbol, the states that go to it on reading that
+    /// symbol.
+    fn predecessors(&self) -> Vec<Vec<Vec<usize>>> {
+        let symbols = self.transitions.first().map_or(0, Vec::len);
+        let mut predecessors = vec![vec![vec![]; symbols]; self.states()];
+        for (from, row) in self.transitions.iter().enumerate() {
+            for (symbol, &to) in row.iter().enumerate() {
+                predecessors[to][symbol].push(from);
+            }
+        }
+        predecessors
+    }
+
+    /// Every automaton with `states` states over `symbols` symbols, up to
+    /// renumbering, in which every state can be reached.
+    fn all(states: usize, symbols: usize) -> Vec<Self> {
+        let mut all = vec![];
+        let mut cells = vec![0; states * symbols];
+        enumerate_cells(&mut cells, 1, 0, states, &mut all);
+
+        all.into_iter()
+            .map(|cells| Dfa {
+                transitions: cells.chunks(symbols).map(<[usize]>::to_vec).collect(),
+            })
+            .collect()
+    }
+}
+
+/// Fill in `cells`, the transitions of an automaton row by row, from `idx`
+/// on. A state is only used once every state numbered before it has been,
+/// so each automaton is only found once.
+fn enumerate_cells(
+    cells: &mut Vec<usize>,
+    idx: usize,
+    used: usize,
+    states: usize,
+    all: &mut Vec<Vec<usize>>,
+) {
+    let symbols = cells.len() / states;
+
+    // A state can only be reached by a transition from a state before it.
+    if used < states - 1 && idx >= (used + 1) * symbols {
+        return;
+    }
+    if idx == cells.len() {
+        all.push(cells.clone());
+        return;
+    }
+
+    for to in 0..=(used + 1).min(states - 1) {
+        cells[idx] = to;
+        enumerate_cells(cells, idx + 1, used.max(to), states, all);
+    }
+}
+
+/// A program's instructions by the numbers used in a [`Certificate`], or
+/// `None` where it halts or is undefined.
+pub(crate) type Table = Vec<Vec<Option<(usize, usize, Direction)>>>;
+
+pub(crate) fn table<S: State, Sym: Symbol>(prog: &Program<S, Sym>) -> Table {
+    let states = prog.states();
+    let symbols = prog.symbols();
+    let index = |symbol: Sym| symbols.iter().position(|&s| s == symbol);
+
+    states
+        .iter()
+        .map(|&state| {
+            symbols
+                .iter()
+                .map(|&symbol| match prog.instruction(state, symbol) {
+                    Some(&(next, write, direction)) if next != S::halt() => Some((
+                        states.iter().position(|&s| s == next)?,
+                        index(write)?,
+                        direction,
+                    )),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The tuples the configurations one step on from those with `tuple` can
+/// have, or
+/// `None` if it halts.
+fn successors(
+    table: &Table,
+    left: &Dfa,
+    right: &Dfa,
+    left_predecessors: &[Vec<Vec<usize>>],
+    right_predecessors: &[Vec<Vec<usize>>],
+    (l, state, read, r): (usize, usize, usize, usize),
+) -> Option<Vec<(usize, usize, usize, usize)>> {
+    let (next, write, direction) = table[state][read]?;
+    let symbols = table[state].len();
+
+    let successors = match direction {
+        // The symbol written joins the left side, and the head reads the
+        // first symbol of the right side, which could be any that leads
+        // `right` to `r`.
+        Direction::Right => (0..symbols)
+            .flat_map(|symbol| {
+                let l = left.transitions[l][write];
+                right_predecessors[r][symbol]
+                    .iter()
+                    .map(move |&r| (l, next, symbol, r))
+            })
+            .collect(),
+        Direction::Left => (0..symbols)
+            .flat_map(|symbol| {
+                let r = right.transitions[r][write];
+                left_predecessors[l][symbol]
+                    .iter()
+                    .map(move |&l| (l, next, symbol, r))
+            })
+            .collect(),
+    };
+
+    Some(successors)
+}
+
+/// The smallest set of tuples closed under the program's transitions that
+/// has the blank tape, if it doesn't have a tape the program halts on.
+fn close(table: &Table, left: &Dfa, right: &Dfa) -> Option<BTreeSet<(usize, usize, usize, usize)>> {
+    let left_predecessors = left.predecessors();
+    let right_predecessors = right.predecessors();
+
+    let mut accepted = BTreeSet::new();
+    let mut todo = vec![(0, 0, 0, 0)];
+
+    while let Some(tuple) = todo.pop() {
+        if !accepted.insert(tuple) {
+            continue;
+        }
+        let successors = successors(
+            table,
+            left,
+            right,
+            &left_predecessors,
+            &right_predecessors,
+            tuple,
+        )?;
+        todo.extend(
+            successors
+                .into_iter()
+                .filter(|tuple| !accepted.contains(tuple)),
+        );
+    }
+
+    Some(accepted)
+}
+
+/// Search for a [`Certificate`] that the program never halts, trying every
+/// pair of automata with up to `max_states` states each, smallest first,
+/// until `deadline` passes.
+pub fn find_closed_language<S: State, Sym: Symbol>(
+    prog: &Program<S, Sym>,
+    max_states: usize,
+    deadline: Option<Instant>,
+) -> Option<Certificate> {
+    let table = table(prog);
+    let symbols = prog.symbols().len();
+    let automata = (1..=max_states)
+        .map(|states| Dfa::all(states, symbols))
+        .collect::<Vec<_>>();
+
+    for size in 0..automata.len() {
+        let pairs = automata[..=size].iter().flatten().flat_map(|left| {
+            automata[..=size]
+                .iter()
+                .flatten()
+                .map(move |right| (left, right))
+        });
+        for (left, right) in pairs {
+            // Pairs where both are smaller have already been tried.
+            if left.states() <= size && right.states() <= size {
+                continue;
+            }
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                return None;
+            }
+            if let Some(accepted) = close(&table, left, right) {
+                return Some(Certificate {
+                    left: left.clone(),
+                    right: right.clone(),
+                    accepted,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+impl Certificate {
+    /// The number of states of the larger automaton.
+    pub fn states(&self) -> usize {
+        self.left.states().max(self.right.states())
+    }
+
+    /// Check that the certificate proves the program never halts: it has the
+    /// blank tape, every tuple it accepts steps to others it accepts, and
+    /// none halt.
+    pub fn verify<S: State, Sym: Symbol>(&self, prog: &Program<S, Sym>) -> Result<(), String> {
+        let table = table(prog);
+        let states = table.len();
+        let symbols = prog.symbols().len();
+
+        for (name, dfa) in [("left", &self.left), ("right", &self.right)] {
+            if dfa.states() == 0 || dfa.transitions.iter().any(|row| row.len() != symbols) {
+                return Err(format!("The {} automaton needs a row for each state", name));
+            }
+            if dfa.transitions.iter().flatten().any(|&q| q >= dfa.states()) {
+                return Err(format!("The {} automaton goes to a missing state", name));
+            }
+            if dfa.transitions[0][0] != 0 {
+                return Err(format!("The {} automaton must stay put on blanks", name));
+            }
+        }
+
+        if !self.accepted.contains(&(0, 0, 0, 0)) {
+            return Err("The blank tape isn't accepted".to_string());
+        }
+
+        let left_predecessors = self.left.predecessors();
+        let right_predecessors = self.right.predecessors();
+
+        for &tuple in &self.accepted {
+            let (l, state, read, r) = tuple;
+            if l >= self.left.states()
+                || state >= states
+                || read >= symbols
+                || r >= self.right.states()
+            {
+                return Err(format!("{:?} is out of range", tuple));
+            }
+
+            let successors = successors(
+                &table,
+                &self.left,
+                &self.right,
+                &left_predecessors,
+                &right_predecessors,
+                tuple,
+            )
+            .ok_or_else(|| format!("{:?} halts", tuple))?;
+
+            if let Some(missing) = successors.iter().find(|t| !self.accepted.contains(t)) {
+                return Err(format!(
+                    "{:?} steps to {:?}, which isn't accepted",
+                    tuple, missing
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    fn find(prog: &str, max_states: usize) -> Option<Certificate> {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => {
+                let certificate = find_closed_language(&prog, max_states, None);
+                if let Some(certificate) = &certificate {
+                    assert_eq!(certificate.verify(&prog), Ok(()));
+                }
+                certificate
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_dfa_all() {
+        assert_eq!(Dfa::all(1, 2).len(), 1);
+        assert_eq!(Dfa::all(2, 2).len(), 4);
+        assert!(Dfa::all(3, 2).iter().all(|dfa| dfa.transitions[0][0] == 0));
+    }
+
+    #[test]
+    fn test_closed_language() {
+        let certificate = find("1RB 1LB  1LA 0RB", 2).unwrap();
+
+        assert!(certificate.verify(&prog("1RB 1LB  1LA 1RH")).is_err());
+        assert!(find("1RB 1LA  1LA 1RB", 2).is_some());
+    }
+
+    #[test]
+    fn test_closed_language_halts() {
+        assert_eq!(find("1RB 1LB  1LA 1RH", 3), None);
+        assert_eq!(find("1RB 1LB  1LA ...", 3), None);
+    }
+
+    #[test]
+    fn test_closed_language_invalid() {
+        let prog = prog("1RB 1LB  1LA 0RB");
+        let mut certificate = find_closed_language(&prog, 2, None).unwrap();
+        let last = *certificate.accepted.iter().last().unwrap();
+        certificate.accepted.remove(&last);
+
+        assert!(certificate.verify(&prog).is_err());
+    }
+
+    fn prog(prog: &str) -> Program<crate::types::StateT<'B'>, crate::types::SymbolT<'1'>> {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => prog,
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    machine::{Configuration, Halt, HaltReason, Machine, Space},
+    tape::Tape,
+    types::{State, Symbol},
+};
+use std::collections::BTreeMap;
+
+/// A hash of a machine's configuration, kept up to date a step at a time.
+///
+/// Each cell that isn't blank has a hash of its position and symbol, and
+/// these are combined with xor, so a step only changes the hash of the cell
+/// it writes. The state and head are mixed in when the hash is read.
+struct Hasher<S, Sym> {
+    states: Vec<S>,
+    symbols: Vec<Sym>,
+    cells: u64,
+}
+
+impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Hasher<S, Sym> {
+    fn new<T: Tape<Sym> + Send + Sync>(machine: &Machine<S, Sym, T>) -> Self {
+        let mut hasher = Self {
+            states: machine.program().states(),
+            symbols: machine.program().symbols(),
+            cells: 0,
+        };
+        let tape = machine.tape();
+        for (pos, symbol) in (tape.leftmost()..).zip(tape.iter()) {
+            hasher.cells ^= hasher.cell(pos, symbol);
+        }
+        hasher
+    }
+
+    fn cell(&self, pos: i64, symbol: Sym) -> u64 {
+        if symbol == Sym::zero() {
+            return 0;
+        }
+        let idx = self.symbols.iter().position(|&s| s == symbol);
+        let idx = idx.unwrap_or(self.symbols.len());
+        mix((pos as u64) << 8 ^ idx as u64)
+    }
+
+    fn hash<T: Tape<Sym> + Send + Sync>(&self, machine: &Machine<S, Sym, T>) -> u64 {
+        let state = self.states.iter().position(|&s| s == machine.state());
+        let state = state.unwrap_or(self.states.len());
+        self.cells ^ mix(mix(machine.head() as u64) ^ state as u64)
+    }
+
+    /// Step the machine, or return `false` if it halted.
+    fn step<T: Tape<Sym> + Send + Sync>(&mut self, machine: &mut Machine<S, Sym, T>) -> bool {
+        let head = machine.head();
+        let step = match machine.step() {
+            Some(step) if step.halt.is_none() => step,
+            _ => return false,
+        };
+        if let Some((_, write, _)) = step.instruction {
+            self.cells ^= self.cell(head, step.read) ^ self.cell(head, write);
+        }
+        true
+    }
+}
+
+/// The finalizer of SplitMix64, which spreads every bit of `x` over the
+/// whole result.
+fn mix(x: u64) -> u64 {
+    let x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Whether two configurations have the same state, head and tape, apart from
+/// blanks at the ends.
+fn same<S: State, Sym: Symbol>(a: &Configuration<S, Sym>, b: &Configuration<S, Sym>) -> bool {
+    let first = a.leftmost.min(b.leftmost);
+    let last = (a.leftmost + a.tape.len() as i64).max(b.leftmost + b.tape.len() as i64);
+
+    a.state == b.state && a.head == b.head && (first..last).all(|pos| a.read(pos) == b.read(pos))
+}
+
+/// Run the machine for up to `limit` steps, watching for a cycler: a machine
+/// that comes back to exactly the configuration it was in before, so will go
+/// round the same steps forever.
+///
+/// If one is found the machine is stopped with [`HaltReason::Cycler`] and the
+/// shortest period, with the first step of the cycle as the halt step.
+/// Otherwise it halts as it would have done or stops with
+/// [`HaltReason::XLimit`] at `limit`.
+///
+/// This is much cheaper than the recurrence check, as it only keeps a hash
+/// of the configuration, updated on each step, and one earlier
+/// configuration. That is saved at steps that are powers of two apart and
+/// compared with each step after it, which is Brent's cycle detection. When
+/// a cycle is found, the machine is run again from where it was given,
+/// alongside a copy a period ahead, to find where the cycle starts.
+pub fn check_cycler<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    machine: &mut Machine<S, Sym, T>,
+    limit: usize,
+) {
+    if machine.halt().is_some() {
+        return;
+    }
+
+    let initial = copy(machine);
+    let mut hasher = Hasher::new(machine);
+    let mut saved = (hasher.hash(machine), machine.configuration());
+    let (mut power, mut period) = (1, 0);
+
+    while machine.steps() < limit {
+        if !hasher.step(machine) {
+            return;
+        }
+        period += 1;
+
+        let hash = hasher.hash(machine);
+        if hash == saved.0 {
+            let configuration = machine.configuration();
+            if same(&saved.1, &configuration) {
+                let start = cycle_start(initial, period);
+                machine.set_halt(Halt::new(start, HaltReason::Cycler(period)));
+                return;
+            }
+        }
+
+        if period == power {
+            saved = (hash, machine.configuration());
+            power *= 2;
+            period = 0;
+        }
+    }
+
+    machine.set_halt(Halt::new(limit, HaltReason::XLimit));
+}
+
+/// A machine in the same configuration, without what it has recorded along
+/// the way, which finding a cycle doesn't need.
+fn copy<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    machine: &Machine<S, Sym, T>,
+) -> Machine<S, Sym, T> {
+    Machine::from_parts(
+        machine.program().clone(),
+        machine.state(),
+        machine.head(),
+        machine.tape().clone(),
+        machine.steps(),
+        machine.mark_count(),
+        BTreeMap::new(),
+        None,
+        Space::new(machine.head()),
+    )
+}
+
+/// The first step of a cycle of `period` steps that `machine` runs into.
+fn cycle_start<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    mut machine: Machine<S, Sym, T>,
+    period: usize,
+) -> usize {
+    // These have no deadline, as finding the start can't be cut short, and
+    // only takes as long again.
+    let mut ahead = copy(&machine);
+    let mut hasher = Hasher::new(&machine);
+    let mut ahead_hasher = Hasher::new(&ahead);
+    for _ in 0..period {
+        ahead_hasher.step(&mut ahead);
+    }
+
+    while hasher.hash(&machine) != ahead_hasher.hash(&ahead)
+        || !same(&machine.configuration(), &ahead.configuration())
+    {
+        hasher.step(&mut machine);
+        ahead_hasher.step(&mut ahead);
+    }
+
+    machine.steps()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    fn check(prog: &str, limit: usize) -> Halt {
+        if let ProgramT::TwoTwo(prog) = parse_program(prog).unwrap() {
+            let mut machine = Machine::new(prog);
+            check_cycler(&mut machine, limit);
+            machine.halt().cloned().unwrap()
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_cycler() {
+        // Goes back and forth over the same two cells.
+        assert_eq!(
+            check("1RB 1RB  1LA 1LA", 1000),
+            Halt::new(2, HaltReason::Cycler(2))
+        );
+        // Erases and rewrites the first cell every other time round.
+        assert_eq!(
+            check("1RB 0RB  1LA 1LA", 1000),
+            Halt::new(2, HaltReason::Cycler(4))
+        );
+    }
+
+    #[test]
+    fn test_cycler_mid_run() {
+        // The cycle is found from the configuration the machine was given, so
+        // one already in it starts where it was given.
+        if let ProgramT::TwoTwo(prog) = parse_program("1RB 0RB  1LA 1LA").unwrap() {
+            for (steps, start) in [(1, 2), (5, 5)] {
+                let mut machine = Machine::new(prog.clone());
+                for _ in 0..steps {
+                    machine.step();
+                }
+                check_cycler(&mut machine, 1000);
+                assert_eq!(
+                    machine.halt(),
+                    Some(&Halt::new(start, HaltReason::Cycler(4)))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_cycler() {
+        assert_eq!(
+            check("1RB 1LB  1LA 1RH", 100),
+            Halt::new(6, HaltReason::Halt)
+        );
+        // Moves along the tape as it cycles.
+        assert_eq!(
+            check("1RB 1RB  1RA 1RA", 1000),
+            Halt::new(1000, HaltReason::XLimit)
+        );
+        assert_eq!(
+            check("1RB 1LA  1LA 1RB", 1000),
+            Halt::new(1000, HaltReason::XLimit)
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    machine::{Configuration, Halt, HaltReason, Machine},
+    tape::Tape,
+    types::{State, Symbol},
+};
+
+/// A step on which the head reached a cell further out on one side than it
+/// had ever been before.
+struct Record<S, Sym> {
+    configuration: Configuration<S, Sym>,
+    /// The furthest the head went back towards the other side, between this
+    /// record and the next.
+    turn: i64,
+}
+
+/// Run the machine for up to `limit` steps, watching for a translated cycler:
+/// a machine that repeats the same sequence of steps forever, each time
+/// shifted along the tape.
+///
+/// If one is found the machine is stopped with
+/// [`HaltReason::TranslatedCycler`], with the step the cycle starts on as
+/// the halt step. Otherwise it halts as it would have done or stops with
+/// [`HaltReason::XLimit`] at `limit`. Cyclers that don't move along the
+/// tape are not found.
+///
+/// Whenever the head reaches a new rightmost cell, the configuration is
+/// compared to the earlier ones where it did so in the same state. If the
+/// head has stayed right of some cell since then, and the tape from that
+/// cell out is the same but shifted, then everything that happened since
+/// will happen again, shifted again, forever. The same goes for the left.
+pub fn check_translated_cycler<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    machine: &mut Machine<S, Sym, T>,
+    limit: usize,
+) {
+    if machine.halt().is_some() {
+        return;
+    }
+
+    let mut rights = vec![Record {
+        configuration: machine.configuration(),
+        turn: machine.head(),
+    }];
+    let mut lefts = vec![Record {
+        configuration: machine.configuration(),
+        turn: machine.head(),
+    }];
+    let (mut rightmost, mut leftmost) = (machine.head(), machine.head());
+
+    while machine.steps() < limit {
+        if machine.step().and_then(|step| step.halt).is_some() {
+            return;
+        }
+
+        let head = machine.head();
+
+        if head > rightmost {
+            rightmost = head;
+
+            let configuration = machine.configuration();
+            if let Some(halt) = find_cycle(&rights, &configuration, Side::Right) {
+                machine.set_halt(halt);
+                return;
+            }
+            rights.push(Record {
+                configuration,
+                turn: head,
+            });
+        } else if head < leftmost {
+            leftmost = head;
+
+            let configuration = machine.configuration();
+            if let Some(halt) = find_cycle(&lefts, &configuration, Side::Left) {
+                machine.set_halt(halt);
+                return;
+            }
+            lefts.push(Record {
+                configuration,
+                turn: head,
+            });
+        }
+
+        if let Some(record) = rights.last_mut() {
+            record.turn = record.turn.min(head);
+        }
+        if let Some(record) = lefts.last_mut() {
+            record.turn = record.turn.max(head);
+        }
+    }
+
+    machine.set_halt(Halt::new(limit, HaltReason::XLimit));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// The most recent earlier record that `current` is a shifted repeat of.
+fn find_cycle<S: State, Sym: Symbol>(
+    records: &[Record<S, Sym>],
+    current: &Configuration<S, Sym>,
+    side: Side,
+) -> Option<Halt> {
+    let mut turn = current.head;
+
+    for record in records.iter().rev() {
+        turn = match side {
+            Side::Right => turn.min(record.turn),
+            Side::Left => turn.max(record.turn),
+        };
+
+        let earlier = &record.configuration;
+        if earlier.state != current.state {
+            continue;
+        }
+
+        let offset = current.head - earlier.head;
+        let (first, last) = match side {
+            Side::Right => (turn, end(earlier).max(end(current) - offset)),
+            Side::Left => (earlier.leftmost.min(current.leftmost - offset), turn + 1),
+        };
+
+        if (first..last).all(|pos| earlier.read(pos) == current.read(pos + offset)) {
+            let reason = HaltReason::TranslatedCycler(current.step - earlier.step, offset);
+            return Some(Halt::new(earlier.step, reason));
+        }
+    }
+
+    None
+}
+
+fn end<S, Sym>(configuration: &Configuration<S, Sym>) -> i64 {
+    configuration.leftmost + configuration.tape.len() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    fn check(prog: &str, limit: usize) -> Halt {
+        if let ProgramT::TwoTwo(prog) = parse_program(prog).unwrap() {
+            let mut machine = Machine::new(prog);
+            check_translated_cycler(&mut machine, limit);
+            machine.halt().cloned().unwrap()
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_translated_cycler() {
+        // Writes 1s forever going right.
+        assert_eq!(
+            check("1RB 1RB  1RA 1RA", 100),
+            Halt::new(0, HaltReason::TranslatedCycler(2, 2))
+        );
+        // Erases as it goes left.
+        assert_eq!(
+            check("0LA 0LA  0LA 0LA", 100).reason,
+            HaltReason::TranslatedCycler(1, -1)
+        );
+    }
+
+    #[test]
+    fn test_translated_cycler_late() {
+        // Lin and Rado's recurrence from step 7, found on the next record.
+        assert_eq!(
+            check("1RB 1LB  1LA 0RB", 1000),
+            Halt::new(8, HaltReason::TranslatedCycler(3, 1))
+        );
+    }
+
+    #[test]
+    fn test_not_translated_cycler() {
+        assert_eq!(
+            check("1RB 1LB  1LA 1RH", 100),
+            Halt::new(6, HaltReason::Halt)
+        );
+        // Bounces back and forth, over more of the tape each time.
+        assert_eq!(
+            check("1RB 1LA  1LA 1RB", 1000),
+            Halt::new(1000, HaltReason::XLimit)
+        );
+        // Cycles without moving along the tape.
+        assert_eq!(
+            check("1RB 1RB  1LA 1LA", 1000),
+            Halt::new(1000, HaltReason::XLimit)
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    machine::Machine,
+    tape::Tape,
+    types::{State, Symbol},
+};
+use std::{collections::BTreeMap, io::Write};
+
+type Rgb = [u8; 3];
+
+/// Symbols from `0` up, the blank symbol is black.
+const SYMBOL_COLORS: &[Rgb] = &[
+    [0, 0, 0],
+    [255, 255, 255],
+    [230, 159, 0],
+    [86, 180, 233],
+    [0, 158, 115],
+    [240, 228, 66],
+    [0, 114, 178],
+    [213, 94, 0],
+    [204, 121, 167],
+    [128, 128, 128],
+];
+
+const HEAD_COLOR: Rgb = [255, 0, 0];
+
+/// A space-time diagram, one row of pixels per sampled step, with the
+/// leftmost cell the head reached in the first column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagram {
+    pub width: usize,
+    pub height: usize,
+    /// Row by row from the top.
+    pub pixels: Vec<Rgb>,
+}
+
+/// What goes into a [`Diagram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagramOptions {
+    /// The number of steps to run for.
+    pub limit: usize,
+    /// Draw a row every this many steps.
+    pub every: usize,
+    /// Color the head by the state it is in, rather than always red.
+    pub color_states: bool,
+}
+
+impl Diagram {
+    /// Run the machine for up to `options.limit` steps, drawing its tape
+    /// before the first step and after every `options.every` steps. The
+    /// machine is left where it stopped.
+    pub fn draw<S: State + Send + Sync, Sym: Symbol + Send + Sync, T: Tape<Sym> + Send + Sync>(
+        machine: &mut Machine<S, Sym, T>,
+        options: DiagramOptions,
+    ) -> Self {
+        let every = options.every.max(1);
+
+        let symbol_colors: BTreeMap<Sym, Rgb> = machine
+            .program()
+            .symbols()
+            .into_iter()
+            .zip(SYMBOL_COLORS.iter().copied())
+            .collect();
+        let states = machine.program().states();
+        let state_colors: BTreeMap<S, Rgb> = states
+            .iter()
+            .enumerate()
+            .map(|(i, &s)| (s, hue((i as f64 + 0.5) / states.len() as f64)))
+            .collect();
+        let head_color = |state: &S| {
+            if options.color_states {
+                state_colors.get(state).copied().unwrap_or(HEAD_COLOR)
+            } else {
+                HEAD_COLOR
+            }
+        };
+
+        let mut rows = vec![];
+        loop {
+            if machine.steps().is_multiple_of(every) {
+                rows.push(machine.configuration());
+            }
+            if machine.steps() >= options.limit {
+                break;
+            }
+            match machine.step() {
+                Some(step) if step.instruction.is_some() => (),
+                _ => break,
+            }
+        }
+        // The last step is always drawn.
+        if rows.last().map(|c| c.step) != Some(machine.steps()) {
+            rows.push(machine.configuration());
+        }
+
+        let first = rows.iter().map(|c| c.leftmost).min().unwrap_or(0);
+        let last = rows
+            .iter()
+            .map(|c| c.leftmost + c.tape.len() as i64)
+            .max()
+            .unwrap_or(1);
+        let width = (last - first) as usize;
+
+        let mut pixels = vec![SYMBOL_COLORS[0]; width * rows.len()];
+        for (y, row) in rows.iter().enumerate() {
+            let offset = y * width + (row.leftmost - first) as usize;
+            for (x, symbol) in row.tape.iter().enumerate() {
+                pixels[offset + x] = symbol_colors
+                    .get(symbol)
+                    .copied()
+                    .unwrap_or(SYMBOL_COLORS[0]);
+            }
+            pixels[y * width + (row.head - first) as usize] = head_color(&row.state);
+        }
+
+        Self {
+            width,
+            height: rows.len(),
+            pixels,
+        }
+    }
+
+    /// Write the diagram as a binary PPM image.
+    pub fn write_ppm<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for pixel in &self.pixels {
+            w.write_all(pixel)?;
+        }
+        Ok(())
+    }
+
+    /// Write the diagram as a PNG image.
+    pub fn write_png<W: Write>(&self, w: W) -> std::io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data = self.pixels.iter().flatten().copied().collect::<Vec<_>>();
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&data))
+            .map_err(std::io::Error::other)
+    }
+}
+
+/// A fully saturated color, `h` of the way round the color wheel.
+fn hue(h: f64) -> Rgb {
+    let h = h * 6.0;
+    let x = ((1.0 - (h % 2.0 - 1.0).abs()) * 255.0) as u8;
+    match h as u32 {
+        0 => [255, x, 0],
+        1 => [x, 255, 0],
+        2 => [0, 255, x],
+        3 => [0, x, 255],
+        4 => [x, 0, 255],
+        _ => [255, 0, x],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    const B: Rgb = [0, 0, 0];
+    const W: Rgb = [255, 255, 255];
+    const H: Rgb = HEAD_COLOR;
+
+    fn diagram(prog: &str, options: DiagramOptions) -> Diagram {
+        if let ProgramT::TwoTwo(prog) = parse_program(prog).unwrap() {
+            Diagram::draw(&mut Machine::new(prog), options)
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_diagram() {
+        let options = DiagramOptions {
+            limit: 100,
+            every: 1,
+            color_states: false,
+        };
+        let diagram = diagram("1RB 1LB  1LA 1RH", options);
+
+        assert_eq!((diagram.width, diagram.height), (4, 7));
+        assert_eq!(
+            diagram.pixels,
+            vec![
+                B, B, H, B, //
+                B, B, W, H, //
+                B, B, H, W, //
+                B, H, W, W, //
+                H, W, W, W, //
+                W, H, W, W, //
+                W, W, H, W, //
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagram_sampled() {
+        let options = DiagramOptions {
+            limit: 1000,
+            every: 100,
+            color_states: true,
+        };
+        let diagram = diagram("1RB 0LB  1LA 0RB", options);
+
+        assert_eq!(diagram.height, 11);
+        assert_eq!(diagram.pixels.len(), diagram.width * 11);
+        assert!(!diagram.pixels.contains(&H));
+        assert!(diagram.pixels.contains(&hue(0.25)) || diagram.pixels.contains(&hue(0.75)));
+
+        let mut ppm = vec![];
+        diagram.write_ppm(&mut ppm).unwrap();
+        let header = format!("P6\n{} 11\n255\n", diagram.width);
+
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(ppm.len(), header.len() + diagram.width * 11 * 3);
+
+        let mut png = vec![];
+        diagram.write_png(&mut png).unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
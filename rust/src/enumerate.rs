@@ -0,0 +1,130 @@
+use crate::{
+    machine::{Halt, HaltReason, Machine},
+    program::Program,
+    types::{Direction, State, Symbol},
+};
+
+/// Tree Normal Form enumeration of every program of a given complexity.
+///
+/// Starting from a program with every instruction undefined, each program is
+/// run until it halts, reaches the step limit or reaches an undefined
+/// instruction. Programs are yielded along with how they stopped. When an
+/// undefined instruction is reached, the program is also expanded into one
+/// child per possible instruction, where only states and symbols that have
+/// already been used, plus the next unused one, may be chosen. This removes
+/// programs that differ only by renaming states or symbols. The first
+/// instruction always moves right, removing left/right mirror images.
+///
+/// A program is not expanded at its last undefined instruction, since the
+/// fully defined programs cannot halt.
+pub struct Enumerate<S, Sym> {
+    stack: Vec<Program<S, Sym>>,
+    states: Vec<S>,
+    symbols: Vec<Sym>,
+    limit: usize,
+    check_recurrence: Option<usize>,
+}
+
+impl<S: State + Send + Sync, Sym: Symbol + Send + Sync> Enumerate<S, Sym> {
+    pub fn new(limit: usize, check_recurrence: Option<usize>) -> Self {
+        Self::with_size(
+            S::states().len(),
+            Sym::symbols().len(),
+            limit,
+            check_recurrence,
+        )
+    }
+
+    /// Enumerate programs with only the first `states` states and `symbols`
+    /// symbols, for types like `DynState` that support more than are wanted.
+    pub fn with_size(
+        states: usize,
+        symbols: usize,
+        limit: usize,
+        check_recurrence: Option<usize>,
+    ) -> Self {
+        Self {
+            stack: vec![Program::undefined_with_size(states, symbols)],
+            states: S::states().into_iter().take(states).collect(),
+            symbols: Sym::symbols().into_iter().take(symbols).collect(),
+            limit,
+            check_recurrence,
+        }
+    }
+
+    fn expand(&mut self, program: &Program<S, Sym>, state: S, symbol: Sym) {
+        let mut defined = 0;
+        let mut undefined = 0;
+        let mut max_state = 0;
+        let mut max_symbol = 0;
+
+        for (_, instr) in program.instructions() {
+            if let Some((st, sym, _)) = instr {
+                defined += 1;
+                if let Some(i) = self.states.iter().position(|s| s == st) {
+                    max_state = max_state.max(i);
+                }
+                if let Some(i) = self.symbols.iter().position(|s| s == sym) {
+                    max_symbol = max_symbol.max(i);
+                }
+            } else {
+                undefined += 1;
+            }
+        }
+
+        if undefined <= 1 {
+            return;
+        }
+
+        let directions: &[Direction] = if defined == 0 {
+            &[Direction::Right]
+        } else {
+            &[Direction::Right, Direction::Left]
+        };
+
+        let states = &self.states[..(max_state + 2).min(self.states.len())];
+        let symbols = &self.symbols[..(max_symbol + 2).min(self.symbols.len())];
+
+        for &st in states.iter().rev() {
+            for &sym in symbols.iter().rev() {
+                for &dir in directions.iter().rev() {
+                    let mut child = program.clone();
+                    child.set_instruction(state, symbol, Some((st, sym, dir)));
+                    self.stack.push(child);
+                }
+            }
+        }
+    }
+}
+
+impl<S: State + Send + Sync, Sym: Symbol + Send + Sync> Iterator for Enumerate<S, Sym> {
+    type Item = (Program<S, Sym>, Halt);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let program = self.stack.pop()?;
+
+        let mut machine = Machine::new(program.clone());
+
+        machine.run_until_halt::<std::io::Stdout>(
+            vec![],
+            self.limit,
+            &mut None,
+            self.check_recurrence,
+            None,
+            false,
+            false,
+        );
+
+        let halt = machine
+            .halt()
+            .cloned()
+            .expect("Machine has been run until halt");
+
+        if let HaltReason::Undefined(_) = halt.reason {
+            let (state, symbol) = machine.action();
+            self.expand(&program, state, symbol);
+        }
+
+        Some((program, halt))
+    }
+}
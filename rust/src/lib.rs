@@ -1,5 +1,14 @@
+pub mod certificate;
+pub mod checkpoint;
+pub mod decider;
+pub mod diagram;
+pub mod enumerate;
 pub mod machine;
+pub mod macro_machine;
 pub mod parse;
+pub mod pipeline;
 pub mod program;
+pub mod report;
+pub mod results;
 pub mod tape;
 pub mod types;
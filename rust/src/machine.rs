@@ -1,44 +1,212 @@
 use crate::{
-    program::{Action, Program},
-    tape::Tape,
-    types::{State, Symbol},
+    program::{Action, Instruction, Program},
+    report::{Format, Report},
+    tape::{Tape, VecTape},
+    types::{Direction, State, Symbol},
 };
-use itertools::{Either, EitherOrBoth::*, Itertools};
 use rayon::prelude::*;
-use std::{cmp::Ordering, collections::BTreeMap, io::Write};
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    io::Write,
+    time::{Duration, Instant},
+};
+
+pub(crate) type Beeps<S> = BTreeMap<S, usize>;
+
+/// How often a machine with a deadline checks the time, in steps.
+const DEADLINE_STEPS: usize = 1 << 12;
+pub(crate) type Snapshots<S, Sym, T> = BTreeMap<Action<S, Sym>, Vec<(usize, i64, T, Beeps<S>)>>;
 
-type Beeps<S> = BTreeMap<S, usize>;
-type Snapshots<S, Sym> = BTreeMap<Action<S, Sym>, Vec<(usize, usize, i64, Tape<Sym>, Beeps<S>)>>;
+/// What the recurrence check remembers between steps.
+pub(crate) struct Recurrence<S, Sym, T> {
+    pub(crate) snapshots: Snapshots<S, Sym, T>,
+    /// The head position at every step since `first`.
+    pub(crate) deviations: Vec<i64>,
+    pub(crate) first: usize,
+}
 
-pub struct Machine<State, Symbol> {
+pub struct Machine<State, Symbol, T = VecTape<Symbol>> {
     prog: Program<State, Symbol>,
     state: State,
-    pos: usize,
-    tape: Tape<Symbol>,
+    pos: i64,
+    tape: T,
+    steps: usize,
+    /// The number of marks on the tape, kept up to date for the blank check.
+    marks: usize,
+    /// The last step each state was entered on.
+    beeps: Beeps<State>,
+    recurrence: Option<Recurrence<State, Symbol, T>>,
+    space: Space<State>,
+    /// When to stop with [`HaltReason::XLimit`], whatever is running it.
+    deadline: Option<Instant>,
+    /// The step the time is next checked against `deadline` on, or after.
+    deadline_check: usize,
 
     halt: Option<Halt>,
 }
 
+/// How far the head of a [`Machine`] has gone, and how many steps it has
+/// taken in each state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Space<State> {
+    /// The leftmost position the head has been at.
+    pub leftmost: i64,
+    /// The rightmost position the head has been at.
+    pub rightmost: i64,
+    /// The number of steps taken from each state.
+    pub visits: BTreeMap<State, usize>,
+}
+
+impl<S: State> Space<S> {
+    /// No steps taken yet, with the head at `head`.
+    pub fn new(head: i64) -> Self {
+        Self {
+            leftmost: head,
+            rightmost: head,
+            visits: BTreeMap::new(),
+        }
+    }
+
+    /// The number of cells the head has been over. The head moves a cell at
+    /// a time, so these are every cell between the extremes.
+    pub fn cells(&self) -> usize {
+        (self.rightmost - self.leftmost + 1) as usize
+    }
+
+    fn record(&mut self, state: S, steps: usize, head: i64) {
+        *self.visits.entry(state).or_default() += steps;
+        self.leftmost = self.leftmost.min(head);
+        self.rightmost = self.rightmost.max(head);
+    }
+}
+
+/// What happened in a single step of a [`Machine`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Step<State, Symbol> {
+    /// The symbol under the head before the step.
+    pub read: Symbol,
+    /// The new state, the symbol written and the direction the head moved,
+    /// or `None` if the instruction was undefined.
+    pub instruction: Option<Instruction<State, Symbol>>,
+    /// Set if the step halted the machine.
+    pub halt: Option<Halt>,
+}
+
 impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Machine<S, Sym> {
     pub fn new(prog: Program<S, Sym>) -> Self {
+        Self::with_tape(prog, VecTape::default())
+    }
+}
+
+impl<
+        S: State + Send + Sync + ToString,
+        Sym: Symbol + Send + Sync + ToString,
+        T: Tape<Sym> + Send + Sync,
+    > Machine<S, Sym, T>
+{
+    pub fn with_tape(prog: Program<S, Sym>, tape: T) -> Self {
         Self {
             prog,
             state: S::initial_state(),
             pos: 0,
-            tape: Tape::default(),
+            marks: tape.marks(),
+            tape,
+            steps: 0,
+            beeps: BTreeMap::new(),
+            recurrence: None,
+            space: Space::new(0),
+            deadline: None,
+            deadline_check: 0,
             halt: None,
         }
     }
 
-    fn read(&self) -> Option<&Sym> {
+    /// Put a machine back together from its parts, see
+    /// [`Checkpoint`](crate::checkpoint::Checkpoint).
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn from_parts(
+        prog: Program<S, Sym>,
+        state: S,
+        pos: i64,
+        tape: T,
+        steps: usize,
+        marks: usize,
+        beeps: Beeps<S>,
+        recurrence: Option<Recurrence<S, Sym, T>>,
+        space: Space<S>,
+    ) -> Self {
+        Self {
+            prog,
+            state,
+            pos,
+            tape,
+            steps,
+            marks,
+            beeps,
+            recurrence,
+            space,
+            deadline: None,
+            deadline_check: 0,
+            halt: None,
+        }
+    }
+
+    pub(crate) fn beeps(&self) -> &Beeps<S> {
+        &self.beeps
+    }
+
+    pub(crate) fn recurrence(&self) -> Option<&Recurrence<S, Sym, T>> {
+        self.recurrence.as_ref()
+    }
+
+    pub(crate) fn mark_count(&self) -> usize {
+        self.marks
+    }
+
+    /// Stop the machine with [`HaltReason::XLimit`] once `deadline` has
+    /// passed, however it is being run. The time is only checked every few
+    /// thousand steps, so it may run on a little.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+        self.deadline_check = self.steps + DEADLINE_STEPS;
+    }
+
+    /// Stop the machine if the deadline has passed, looking at the clock only
+    /// once it has taken another [`DEADLINE_STEPS`] steps since the last look.
+    fn check_deadline(&mut self) {
+        if let Some(deadline) = self.deadline {
+            if self.halt.is_none() && self.steps >= self.deadline_check {
+                self.deadline_check = self.steps + DEADLINE_STEPS;
+                if Instant::now() >= deadline {
+                    self.halt = Some(Halt::new(self.steps, HaltReason::XLimit));
+                }
+            }
+        }
+    }
+
+    /// Stop the machine for a reason found outside of it, by a decider.
+    pub(crate) fn set_halt(&mut self, halt: Halt) {
+        self.halt = Some(halt);
+    }
+
+    fn read(&self) -> Sym {
         self.tape.read(self.pos)
     }
 
     pub fn marks(&self) -> usize {
-        self.tape.iter().filter(|item| Sym::zero() != *item).count()
+        self.tape.marks()
     }
 
     fn write(&mut self, symbol: Sym) {
+        let read = self.read();
+        if Sym::zero() == read && Sym::zero() != symbol {
+            self.marks += 1;
+        } else if Sym::zero() != read && Sym::zero() == symbol {
+            self.marks -= 1;
+        }
         self.tape.write(self.pos, symbol)
     }
 
@@ -54,14 +222,53 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Ma
         self.halt.as_ref()
     }
 
+    pub fn program(&self) -> &Program<S, Sym> {
+        &self.prog
+    }
+
+    /// The position of the head. The machine starts at position 0.
+    pub fn head(&self) -> i64 {
+        self.pos
+    }
+
+    pub fn state(&self) -> S {
+        self.state
+    }
+
+    pub fn tape(&self) -> &T {
+        &self.tape
+    }
+
+    /// How far the head has gone and how long it spent in each state.
+    pub fn space(&self) -> &Space<S> {
+        &self.space
+    }
+
+    /// The number of steps taken so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub(crate) fn action(&self) -> Action<S, Sym> {
+        (self.state, self.read())
+    }
+
+    /// The input is written so that the head starts in its middle, at
+    /// position 0.
     fn input_to_tape(&mut self, input: Vec<Sym>) {
+        let offset = (input.len() / 2) as i64;
         for (i, s) in input.into_iter().enumerate() {
-            self.tape.write(i, s);
+            self.tape.write(i as i64 - offset, s);
         }
+        self.marks = self.tape.marks();
     }
 
-    fn recurr_check_init() -> (Snapshots<S, Sym>, Vec<i64>) {
-        (BTreeMap::new(), vec![])
+    fn min_deviations(deviations: &[i64], dev: i64, pstep: usize) -> i64 {
+        deviations[pstep..].iter().min().copied().unwrap_or(dev)
+    }
+
+    fn max_deviations(deviations: &[i64], dev: i64, pstep: usize) -> i64 {
+        deviations[pstep..].iter().max().copied().unwrap_or(dev) + 1
     }
 
     fn par_min_deviations(deviations: &[i64], dev: i64, pstep: usize) -> i64 {
@@ -72,104 +279,59 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Ma
         deviations[pstep..].par_iter().max().copied().unwrap_or(dev) + 1
     }
 
+    /// The span of the previous tape that must equal the current tape, shifted
+    /// by `dev - pdev`, for the machine to be in a Lin recurrence.
+    ///
+    /// When the head has moved left since the previous snapshot, everything
+    /// left of the furthest right the head has been since then must match.
+    /// When it has moved right, everything right of the furthest left. When it
+    /// hasn't moved, only the cells the head has been on since then.
+    fn recurr_span<Min: FnOnce() -> i64, Max: FnOnce() -> i64>(
+        &self,
+        ptape: &T,
+        pdev: i64,
+        dev: i64,
+        dmin: Min,
+        dmax: Max,
+    ) -> (i64, i64) {
+        let shift = dev - pdev;
+        match dev.cmp(&pdev) {
+            Ordering::Less => (ptape.leftmost().min(self.tape.leftmost() - shift), dmax()),
+            Ordering::Greater => (dmin(), ptape.rightmost().max(self.tape.rightmost() - shift)),
+            Ordering::Equal => (dmin(), dmax()),
+        }
+    }
+
     fn par_recurr_check(
         &mut self,
         step: usize,
-        snaps: &mut Snapshots<S, Sym>,
+        snaps: &mut Snapshots<S, Sym, T>,
         deviations: &[i64],
-        init: usize,
+        first: usize,
         beeps: &Beeps<S>,
         dev: i64,
     ) -> Option<Halt> {
-        let action = (self.state, self.read().copied().unwrap_or_else(Sym::zero));
+        let action = (self.state, self.read());
 
         if let Some(items) = snaps.get(&action).cloned() {
             let iter = items.par_iter();
 
             if let Some((pstep, step, pbeeps, ptape)) = iter
-                .filter_map(|(pstep, pinit, pdev, ptape, pbeeps)| {
-                    let (prev, curr) = match dev.cmp(pdev) {
-                        std::cmp::Ordering::Less => {
-                            let dmax = Self::par_max_deviations(deviations, dev, *pstep);
-
-                            let mut prev = ptape
-                                .iter_to((*pinit as i64 + dmax) as usize)
-                                .collect::<Vec<_>>();
-
-                            let mut curr = self
-                                .tape
-                                .iter_to((init as i64 + dmax + dev - *pdev) as usize)
-                                .collect::<Vec<_>>();
-
-                            match curr.len().cmp(&prev.len()) {
-                                Ordering::Greater => {
-                                    let mut prep = (0..(curr.len() - prev.len()))
-                                        .map(|_| Sym::zero())
-                                        .collect::<Vec<_>>();
-                                    prep.append(&mut prev);
-                                    prev = prep;
-                                }
-                                Ordering::Less => {
-                                    let mut prep = (0..(prev.len() - curr.len()))
-                                        .map(|_| Sym::zero())
-                                        .collect::<Vec<_>>();
-                                    prep.append(&mut curr);
-                                    curr = prep;
-                                }
-                                Ordering::Equal => (),
-                            }
-
-                            (prev, curr)
-                        }
-                        Ordering::Greater => {
-                            let dmin = Self::par_min_deviations(deviations, dev, *pstep);
-
-                            let from_prev = *pinit as i64 + dmin;
-
-                            let mut prev = ptape.iter_from(from_prev).collect::<Vec<_>>();
-
-                            let from_curr = init as i64 + dmin + dev - pdev;
-
-                            let mut curr = self.tape.iter_from(from_curr).collect::<Vec<_>>();
-
-                            match curr.len().cmp(&prev.len()) {
-                                Ordering::Greater => {
-                                    let mut app = (0..(curr.len() - prev.len()))
-                                        .map(|_| Sym::zero())
-                                        .collect::<Vec<_>>();
-                                    prev.append(&mut app);
-                                }
-                                Ordering::Less => {
-                                    let mut app = (0..(prev.len() - curr.len()))
-                                        .map(|_| Sym::zero())
-                                        .collect::<Vec<_>>();
-                                    curr.append(&mut app);
-                                }
-                                Ordering::Equal => (),
-                            }
-
-                            (prev, curr)
-                        }
-                        Ordering::Equal => {
-                            let dmax = Self::par_max_deviations(deviations, dev, *pstep);
-                            let dmin = Self::par_min_deviations(deviations, dev, *pstep);
-
-                            let from_prev = *pinit as i64 + dmin;
-
-                            let prev = ptape
-                                .iter_between(from_prev, *pinit as i64 + dmax)
-                                .collect::<Vec<_>>();
-
-                            let from_curr = init as i64 + dmin;
-
-                            let curr = self
-                                .tape
-                                .iter_between(from_curr, init as i64 + dmax)
-                                .collect::<Vec<_>>();
-
-                            (prev, curr)
-                        }
-                    };
+                .filter_map(|(pstep, pdev, ptape, pbeeps)| {
+                    let (first, last) = self.recurr_span(
+                        ptape,
+                        *pdev,
+                        dev,
+                        || Self::par_min_deviations(deviations, dev, *pstep - first),
+                        || Self::par_max_deviations(deviations, dev, *pstep - first),
+                    );
+                    let shift = dev - pdev;
+
+                    let prev = ptape.iter_between(first, last).collect::<Vec<_>>();
+                    let curr = self
+                        .tape
+                        .iter_between(first + shift, last + shift)
+                        .collect::<Vec<_>>();
 
                     if prev == curr {
                         Some((pstep, step, pbeeps, ptape))
@@ -196,102 +358,41 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Ma
 
         snaps
             .entry(action)
-            .and_modify(|v| v.push((step, init, dev, self.tape.clone(), beeps.clone())))
-            .or_insert_with(|| vec![(step, init, dev, self.tape.clone(), beeps.clone())]);
+            .and_modify(|v| v.push((step, dev, self.tape.clone(), beeps.clone())))
+            .or_insert_with(|| vec![(step, dev, self.tape.clone(), beeps.clone())]);
         None
     }
 
     fn recurr_check(
         &mut self,
         step: usize,
-        snaps: &mut Snapshots<S, Sym>,
+        snaps: &mut Snapshots<S, Sym, T>,
         deviations: &[i64],
-        init: usize,
+        first: usize,
         beeps: &Beeps<S>,
         dev: i64,
     ) -> Option<Halt> {
-        let action = (self.state, self.read().copied().unwrap_or_else(Sym::zero));
-
-        let mut iter = if let Some(items) = snaps.get(&action) {
-            Either::Right(items.iter())
-        } else {
-            Either::Left(std::iter::empty())
-        };
-
-        if let Some((pstep, step, pbeeps, ptape)) = loop {
-            if let Some((pstep, pinit, pdev, ptape, pbeeps)) = iter.next() {
-                let (prev, curr) = match dev.cmp(pdev) {
-                    std::cmp::Ordering::Less => {
-                        let dmax = deviations[*pstep..].iter().max().copied().unwrap_or(dev) + 1;
-
-                        let to_prev = (*pinit as i64 + dmax) as usize;
-
-                        let mut prev =
-                            Box::new(ptape.iter_to(to_prev)) as Box<dyn Iterator<Item = Sym>>;
-
-                        let to_curr = (init as i64 + dmax + dev - *pdev) as usize;
-
-                        let mut curr =
-                            Box::new(self.tape.iter_to(to_curr)) as Box<dyn Iterator<Item = Sym>>;
-
-                        match to_curr.cmp(&to_prev) {
-                            Ordering::Greater => {
-                                let prep = (0..(to_curr - to_prev)).map(|_| Sym::zero());
-
-                                prev = Box::new(prep.chain(prev)) as Box<dyn Iterator<Item = Sym>>;
-                            }
-                            Ordering::Less => {
-                                let prep = (0..(to_prev - to_curr)).map(|_| Sym::zero());
-
-                                curr = Box::new(prep.chain(curr)) as Box<dyn Iterator<Item = Sym>>;
-                            }
-                            Ordering::Equal => (),
-                        }
-
-                        (OfThree::One(prev), OfThree::One(curr))
-                    }
-                    Ordering::Greater => {
-                        let dmin = deviations[*pstep..].iter().min().copied().unwrap_or(dev);
-
-                        let from_prev = *pinit as i64 + dmin;
-
-                        let prev = ptape.iter_from(from_prev);
-
-                        let from_curr = init as i64 + dmin + dev - pdev;
-
-                        let curr = self.tape.iter_from(from_curr);
-
-                        (OfThree::Two(prev), OfThree::Two(curr))
-                    }
-                    Ordering::Equal => {
-                        let dmax = deviations[*pstep..].iter().max().copied().unwrap_or(dev) + 1;
-                        let dmin = deviations[*pstep..].iter().min().copied().unwrap_or(dev);
-
-                        let from_prev = *pinit as i64 + dmin;
-
-                        let prev = ptape.iter_between(from_prev, *pinit as i64 + dmax);
-
-                        let from_curr = init as i64 + dmin;
-
-                        let curr = self.tape.iter_between(from_curr, init as i64 + dmax);
-
-                        (OfThree::Three(prev), OfThree::Three(curr))
-                    }
-                };
+        let action = (self.state, self.read());
+
+        let found = snaps.get(&action).and_then(|items| {
+            items.iter().find(|(pstep, pdev, ptape, _)| {
+                let (first, last) = self.recurr_span(
+                    ptape,
+                    *pdev,
+                    dev,
+                    || Self::min_deviations(deviations, dev, *pstep - first),
+                    || Self::max_deviations(deviations, dev, *pstep - first),
+                );
+                let shift = dev - pdev;
 
-                if prev.zip_longest(curr).all(|both| match both {
-                    Both(p, c) => p == c,
-                    Left(l) => l == Sym::zero(),
-                    Right(r) => r == Sym::zero(),
-                }) {
-                    break Some((*pstep, step, pbeeps, ptape));
-                }
-            } else {
-                break None;
-            }
-        } {
-            self.tape = ptape.clone();
+                ptape
+                    .iter_between(first, last)
+                    .eq(self.tape.iter_between(first + shift, last + shift))
+            })
+        });
 
+        if let Some((pstep, _, ptape, pbeeps)) = found {
+            let pstep = *pstep;
             let reason = if pbeeps
                 .keys()
                 .all(|state| beeps.get(state) > pbeeps.get(state))
@@ -301,70 +402,132 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Ma
                 HaltReason::Quasihalt
             };
 
+            self.tape = ptape.clone();
+
             return Some(Halt::new(pstep, reason(step - pstep)));
         }
 
         snaps
             .entry(action)
-            .and_modify(|v| v.push((step, init, dev, self.tape.clone(), beeps.clone())))
-            .or_insert_with(|| vec![(step, init, dev, self.tape.clone(), beeps.clone())]);
+            .and_modify(|v| v.push((step, dev, self.tape.clone(), beeps.clone())))
+            .or_insert_with(|| vec![(step, dev, self.tape.clone(), beeps.clone())]);
         None
     }
 
-    fn write_tape<B: Write>(&self, output: &mut Option<B>, step: usize) {
-        if let Some(b) = output {
-            let mut buffer = format!("{:8} {:?}  ", step, self.state);
+    /// The current step, state, head position and the written part of the
+    /// tape, which always includes the cell under the head.
+    pub fn configuration(&self) -> Configuration<S, Sym> {
+        let first = self.tape.leftmost().min(self.pos);
+        let last = self.tape.rightmost().max(self.pos + 1);
+
+        Configuration {
+            step: self.steps,
+            state: self.state,
+            head: self.pos,
+            leftmost: first,
+            tape: self.tape.iter_between(first, last).collect(),
+        }
+    }
+
+    /// Step the machine lazily, yielding its configuration before the first
+    /// step and after every step, until it halts or has taken `limit` steps.
+    pub fn configurations(&mut self, limit: usize) -> Configurations<'_, S, Sym, T> {
+        Configurations {
+            machine: self,
+            limit,
+            started: false,
+        }
+    }
 
-            let tape_iter = self.tape.iter();
+    /// Take a single step, or return `None` if the machine has already
+    /// halted. Reaching the halt state or an undefined instruction halts the
+    /// machine, which is reported in the step.
+    pub fn step(&mut self) -> Option<Step<S, Sym>> {
+        if self.halt.is_some() {
+            return None;
+        }
 
-            for (idx, s) in tape_iter.enumerate() {
-                if idx == self.pos {
-                    buffer.push('[');
-                }
-                buffer.push_str(&s.to_string());
-                if idx == self.pos {
-                    buffer.push(']')
-                }
+        let read = self.read();
+        let instruction = self.prog.instruction(self.state, read).copied();
+
+        if let Some((new_state, symbol, direction)) = instruction {
+            let state = self.state;
+            self.state = new_state;
+
+            self.write(symbol);
+
+            match direction {
+                Direction::Left => self.move_left(),
+                Direction::Right => self.move_right(),
+            }
+            self.steps += 1;
+            self.space.record(state, 1, self.pos);
+
+            if self.state == S::halt() {
+                self.halt = Some(Halt::new(self.steps, HaltReason::Halt));
             }
+        } else {
+            let mut undfnd_str = self.state.to_string();
+
+            undfnd_str.push_str(read.to_string().as_str());
 
-            writeln!(b, "{}", buffer).unwrap();
+            self.halt = Some(Halt::new(self.steps + 1, HaltReason::Undefined(undfnd_str)));
         }
+
+        self.check_deadline();
+
+        Some(Step {
+            read,
+            instruction,
+            halt: self.halt.clone(),
+        })
     }
 
-    fn run_turing_step(&mut self, init: &mut usize, marks: &mut usize) -> bool {
-        let read_symbol = self.read().copied().unwrap_or_else(Sym::zero);
+    /// When the instruction for the current state and symbol keeps the state,
+    /// the head sweeps across the whole run of that symbol in one go. Takes
+    /// all of those steps, up to `max`, at once and returns how many there
+    /// were.
+    fn run_chain_step(&mut self, max: usize) -> Option<usize> {
+        let read_symbol = self.read();
         let state = self.state;
 
-        if let Some(&(new_state, symbol, direction)) = self.prog.instruction(state, read_symbol) {
-            self.state = new_state;
+        match self.prog.instruction(state, read_symbol) {
+            Some(&(new_state, symbol, direction)) if new_state == state && state != S::halt() => {
+                let count = self.tape.run_length(self.pos, direction, max as u64);
 
-            if (Sym::zero(), Sym::zero()) == (read_symbol, symbol) {
-            } else if Sym::zero() == read_symbol && Sym::zero() != symbol {
-                *marks += 1;
-            } else if Sym::zero() != read_symbol && Sym::zero() == symbol {
-                *marks -= 1;
-            } else {
-            }
+                if Sym::zero() == read_symbol && Sym::zero() != symbol {
+                    self.marks += count as usize;
+                } else if Sym::zero() != read_symbol && Sym::zero() == symbol {
+                    self.marks -= count as usize;
+                }
 
-            self.write(symbol);
+                self.tape.fill(self.pos, direction, count, symbol);
 
-            match direction {
-                crate::types::Direction::Left => {
-                    if self.pos == 0 {
-                        *init += 1;
-                        self.tape.insert();
-                    } else {
-                        self.move_left();
-                    }
+                match direction {
+                    Direction::Left => self.pos -= count as i64,
+                    Direction::Right => self.pos += count as i64,
                 }
-                crate::types::Direction::Right => self.move_right(),
+                self.steps += count as usize;
+                self.space.record(state, count as usize, self.pos);
+
+                Some(count as usize)
             }
-            true
-        } else {
-            false
+            _ => None,
         }
     }
 
+    /// Run until the machine halts or has taken `limit` steps.
+    ///
+    /// A machine that stopped at an earlier limit carries on from where it
+    /// was, exactly as if it had been run to this limit in one go. The input
+    /// is only written to the tape before the first step.
+    ///
+    /// With `accelerate`, a run of a symbol the head sweeps across without
+    /// changing state is crossed in one go, which is much faster on a
+    /// [`RleTape`](crate::tape::RleTape). Step and mark counts are the same
+    /// either way. Acceleration is not used while checking for recurrence,
+    /// which needs to see every step.
+    #[allow(clippy::too_many_arguments)]
     pub fn run_until_halt<B: Write>(
         &mut self,
         input: Vec<Sym>,
@@ -373,41 +536,62 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Ma
         check_recurrence: Option<usize>,
         check_blank: Option<usize>,
         parallel: bool,
+        accelerate: bool,
     ) {
-        self.input_to_tape(input);
-        let mut init = self.tape.size() / 2;
+        match &self.halt {
+            Some(halt) if halt.is_limit() => self.halt = None,
+            Some(_) => return,
+            None => (),
+        }
 
-        self.pos = init;
+        if self.steps == 0 {
+            self.input_to_tape(input);
 
-        let mut marks = 0;
+            self.pos = 0;
+        }
 
-        let mut beeps: Beeps<S> = BTreeMap::new();
+        let mut beeps = std::mem::take(&mut self.beeps);
 
-        let (mut snapshots, mut deviations) = if check_recurrence.is_some() {
-            let f = Self::recurr_check_init();
-            (Some(f.0), Some(f.1))
-        } else {
-            (None, None)
-        };
+        let mut recurrence = self.recurrence.take();
+        if check_recurrence.is_some() && recurrence.is_none() {
+            recurrence = Some(Recurrence {
+                snapshots: BTreeMap::new(),
+                deviations: vec![],
+                first: self.steps,
+            });
+        }
 
-        for step in 0..=limit {
-            self.write_tape(output, step);
+        let accelerate = accelerate && check_recurrence.is_none();
 
-            let dev = self.pos as i64 - init as i64;
+        while self.steps <= limit {
+            let step = self.steps;
 
-            if let Some(ref mut devs) = deviations {
-                devs.push(dev);
+            if let Some(b) = output {
+                writeln!(b, "{}", self.configuration()).unwrap();
             }
 
-            if let (Some(start), Some(snaps), Some(deviations)) =
-                (check_recurrence, &mut snapshots, &deviations)
+            let dev = self.pos;
+
+            if let Some(Recurrence { deviations, .. }) = &mut recurrence {
+                deviations.push(dev);
+            }
+
+            if let (
+                Some(start),
+                Some(Recurrence {
+                    snapshots,
+                    deviations,
+                    first,
+                }),
+            ) = (check_recurrence, &mut recurrence)
             {
                 if step >= start {
                     if parallel {
                         self.halt =
-                            self.par_recurr_check(step, snaps, deviations, init, &beeps, dev);
+                            self.par_recurr_check(step, snapshots, deviations, *first, &beeps, dev);
                     } else {
-                        self.halt = self.recurr_check(step, snaps, deviations, init, &beeps, dev);
+                        self.halt =
+                            self.recurr_check(step, snapshots, deviations, *first, &beeps, dev);
                     }
                 }
             }
@@ -416,62 +600,121 @@ impl<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString> Ma
             }
 
             beeps.insert(self.state, step);
-            let notundefined = self.run_turing_step(&mut init, &mut marks);
-
-            // Checks for stopping
 
-            if !notundefined {
-                let mut undfnd_str = self.state.to_string();
+            let chain = if accelerate {
+                self.run_chain_step(limit + 1 - step)
+            } else {
+                None
+            };
 
-                undfnd_str.push_str(
-                    self.read()
-                        .copied()
-                        .unwrap_or_else(Sym::zero)
-                        .to_string()
-                        .as_str(),
-                );
+            let taken = match chain {
+                Some(taken) => {
+                    self.check_deadline();
+                    taken
+                }
+                None => match self.step() {
+                    Some(Step {
+                        instruction: Some(_),
+                        ..
+                    }) => 1,
+                    _ => break,
+                },
+            };
 
-                self.halt = Some(Halt::new(step + 1, HaltReason::Undefined(undfnd_str)));
-                break;
-            }
+            // Checks for stopping
 
             if let Some(s) = check_blank {
-                if s <= step && marks == 0 {
-                    self.halt = Some(Halt::new(step + 1, HaltReason::Blanking));
+                if s < step + taken && self.marks == 0 {
+                    self.halt = Some(Halt::new(step + taken, HaltReason::Blanking));
                     break;
                 }
             }
 
-            if self.state == S::halt() {
-                self.halt = Some(Halt::new(step + 1, HaltReason::Halt));
+            if self.halt.is_some() {
                 break;
             }
         }
 
+        self.beeps = beeps;
+        self.recurrence = recurrence;
+
         if self.halt.is_none() {
             self.halt = Some(Halt::new(limit, HaltReason::XLimit));
         }
     }
+
+    /// Write the one line summary of how the machine halted.
+    pub fn write_summary<B: Write + ?Sized>(&self, w: &mut B) -> std::io::Result<()> {
+        match Report::new(self, Duration::ZERO) {
+            Some(report) => report.write(w, Format::Text),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A snapshot of a [`Machine`] between steps.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Configuration<State, Symbol> {
+    pub step: usize,
+    pub state: State,
+    pub head: i64,
+    /// The position of the first cell of `tape`.
+    pub leftmost: i64,
+    pub tape: Vec<Symbol>,
+}
+
+impl<S, Sym: Symbol> Configuration<S, Sym> {
+    /// The symbol at `pos`, which is blank outside the written part.
+    pub fn read(&self, pos: i64) -> Sym {
+        usize::try_from(pos - self.leftmost)
+            .ok()
+            .and_then(|idx| self.tape.get(idx).copied())
+            .unwrap_or_else(Sym::zero)
+    }
+}
+
+impl<S: State, Sym: Symbol> Display for Configuration<S, Sym> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:8} {:?}  ", self.step, self.state)?;
+
+        for (idx, s) in (self.leftmost..).zip(self.tape.iter()) {
+            if idx == self.head {
+                write!(f, "[{}]", s)?;
+            } else {
+                write!(f, "{}", s)?;
+            }
+        }
+        Ok(())
+    }
 }
 
-pub enum OfThree<I1, I2, I3> {
-    One(I1),
-    Two(I2),
-    Three(I3),
+/// The iterator returned by [`Machine::configurations`].
+pub struct Configurations<'a, State, Symbol, T> {
+    machine: &'a mut Machine<State, Symbol, T>,
+    limit: usize,
+    started: bool,
 }
 
-impl<T, I1: Iterator<Item = T>, I2: Iterator<Item = T>, I3: Iterator<Item = T>> Iterator
-    for OfThree<I1, I2, I3>
+impl<
+        'a,
+        S: State + Send + Sync + ToString,
+        Sym: Symbol + Send + Sync + ToString,
+        T: Tape<Sym> + Send + Sync,
+    > Iterator for Configurations<'a, S, Sym, T>
 {
-    type Item = T;
+    type Item = Configuration<S, Sym>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        use OfThree::*;
-        match self {
-            One(i) => i.next(),
-            Two(i) => i.next(),
-            Three(i) => i.next(),
+        if self.started {
+            if self.machine.steps() >= self.limit {
+                return None;
+            }
+            // Nothing changes on an undefined instruction.
+            self.machine.step()?.instruction?;
         }
+        self.started = true;
+
+        Some(self.machine.configuration())
     }
 }
 
@@ -507,55 +750,16 @@ pub enum HaltReason {
     Quasihalt(usize),
     Blanking,
     Undefined(String),
-}
-
-#[allow(clippy::too_many_arguments)]
-pub fn run_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>(
-    program: Program<S, Sym>,
-    prog_str: &str,
-    limit: usize,
-    mut output: Option<Box<dyn Write>>,
-    verbose: bool,
-    check_recurrence: Option<usize>,
-    check_blank: Option<usize>,
-    parallel: bool,
-) -> Machine<S, Sym> {
-    let mut machine = Machine::new(program);
-
-    if verbose {
-        machine.run_until_halt(
-            vec![],
-            limit,
-            &mut output,
-            check_recurrence,
-            check_blank,
-            parallel,
-        );
-    } else {
-        machine.run_until_halt::<std::io::Stdout>(
-            vec![],
-            limit,
-            &mut None,
-            check_recurrence,
-            check_blank,
-            parallel,
-        );
-    }
-
-    if let Some(halt) = machine.halt() {
-        if let Some(w) = &mut output {
-            if let Err(e) = writeln!(
-                w,
-                "{}: marks {} steps {} reason {:?}",
-                prog_str,
-                machine.marks(),
-                halt.steps,
-                halt.reason
-            ) {
-                writeln!(std::io::stderr(), "Error writing: {}", e)
-                    .expect("Unable to write to stderr");
-            }
-        }
-    }
-    machine
+    /// The period, and how far the tape has shifted after each period.
+    TranslatedCycler(usize, i64),
+    /// The steps between the first two sweeps found, and the words the tape
+    /// grows by each sweep.
+    Bouncer(usize, Vec<String>),
+    /// The period of a cycle that comes back to exactly the same
+    /// configuration.
+    Cycler(usize),
+    /// The most steps back a halt could be traced, by backward reasoning.
+    Backward(usize),
+    /// The most states either automaton of a closed tape language has.
+    ClosedLanguage(usize),
 }
@@ -0,0 +1,345 @@
+use crate::{
+    machine::{Halt, HaltReason},
+    program::Program,
+    types::{Direction, State, Symbol},
+};
+use std::{collections::BTreeMap, io::Write};
+
+/// `block_size` cells of the base machine's tape.
+pub type Block<Sym> = Vec<Sym>;
+
+/// What the base machine does from entering a block until it leaves it.
+#[derive(Debug, Clone)]
+struct Transition<S> {
+    /// The id of the block written.
+    block: usize,
+    state: S,
+    /// The side the head leaves the block on.
+    exit: Direction,
+    steps: usize,
+    halt: Option<HaltReason>,
+}
+
+/// A macro machine, which simulates the base program `block_size` cells at a
+/// time.
+///
+/// The head is always on the edge of a block, next to the block on the other
+/// side of it. Macro transitions, from the state, edge and block to the block
+/// written, the new state and which side the head leaves on, are computed
+/// from the base program the first time they are needed. Each block is
+/// numbered the first time it is seen, and the tape is kept as runs of equal
+/// blocks on two stacks either side of the head. When the head would leave
+/// each block of a run the way it came in, in the same state, it crosses the
+/// whole run in one go.
+pub struct MacroMachine<State, Symbol> {
+    prog: Program<State, Symbol>,
+    block_size: usize,
+    state: State,
+    /// The edge of the block the head is on, the left edge of the last block
+    /// of `right` or the right edge of the last block of `left`.
+    edge: Direction,
+    /// Runs of block ids to the left of the head, the last one is next to it.
+    left: Vec<(usize, u64)>,
+    /// Runs of block ids to the right of the head, the last one is next to it.
+    right: Vec<(usize, u64)>,
+    /// The blocks by id, the blank block is `0`.
+    blocks: Vec<Block<Symbol>>,
+    ids: BTreeMap<Block<Symbol>, usize>,
+    transitions: BTreeMap<(State, Direction, usize), Transition<State>>,
+
+    steps: usize,
+    macro_steps: usize,
+    halt: Option<Halt>,
+}
+
+fn push_run(runs: &mut Vec<(usize, u64)>, block: usize, count: u64) {
+    match runs.last_mut() {
+        Some((b, n)) if *b == block => *n += count,
+        _ => runs.push((block, count)),
+    }
+}
+
+impl<S: State + ToString, Sym: Symbol + ToString> MacroMachine<S, Sym> {
+    pub fn new(prog: Program<S, Sym>, block_size: usize) -> Self {
+        assert!(block_size > 0, "Blocks must be at least one cell");
+        let zero = vec![Sym::zero(); block_size];
+        Self {
+            prog,
+            block_size,
+            state: S::initial_state(),
+            edge: Direction::Left,
+            left: vec![],
+            right: vec![],
+            blocks: vec![zero.clone()],
+            ids: std::iter::once((zero, 0)).collect(),
+            transitions: BTreeMap::new(),
+            steps: 0,
+            macro_steps: 0,
+            halt: None,
+        }
+    }
+
+    pub fn halt(&self) -> Option<&Halt> {
+        self.halt.as_ref()
+    }
+
+    pub fn program(&self) -> &Program<S, Sym> {
+        &self.prog
+    }
+
+    /// The number of base machine steps taken.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The number of macro transitions taken, counting each block of a run
+    /// crossed in one go.
+    pub fn macro_steps(&self) -> usize {
+        self.macro_steps
+    }
+
+    pub fn marks(&self) -> usize {
+        self.left
+            .iter()
+            .chain(self.right.iter())
+            .map(|&(block, n)| {
+                self.blocks[block]
+                    .iter()
+                    .filter(|s| **s != Sym::zero())
+                    .count()
+                    * n as usize
+            })
+            .sum()
+    }
+
+    /// The id of a block, numbering it if it hasn't been seen before.
+    fn id(&mut self, block: Block<Sym>) -> usize {
+        match self.ids.get(&block) {
+            Some(&id) => id,
+            None => {
+                let id = self.blocks.len();
+                self.blocks.push(block.clone());
+                self.ids.insert(block, id);
+                id
+            }
+        }
+    }
+
+    /// Run the base program on a single block, from the given edge, until
+    /// the head leaves the block or the machine stops. If the head never
+    /// leaves, that is a [`HaltReason::Cycler`] starting after `steps`.
+    fn compute_transition(&mut self, state: S, edge: Direction, block: usize) -> Transition<S> {
+        let mut block = self.blocks[block].clone();
+        let mut state = state;
+        let mut pos = match edge {
+            Direction::Left => 0,
+            Direction::Right => self.block_size as i64 - 1,
+        };
+        let mut steps = 0;
+
+        // The step each configuration within the block was first seen at, as
+        // the head can only stay in the block forever by repeating one.
+        let mut seen = BTreeMap::new();
+
+        let (exit, halt) = loop {
+            if let Some(start) = seen.insert((state, pos, block.clone()), steps) {
+                let period = steps - start;
+                steps = start;
+                break (edge, Some(HaltReason::Cycler(period)));
+            }
+
+            let symbol = block[pos as usize];
+            let (new_state, new_symbol, direction) = match self.prog.instruction(state, symbol) {
+                Some(&instr) => instr,
+                None => {
+                    let mut undfnd_str = state.to_string();
+                    undfnd_str.push_str(&symbol.to_string());
+                    break (edge, Some(HaltReason::Undefined(undfnd_str)));
+                }
+            };
+
+            block[pos as usize] = new_symbol;
+            state = new_state;
+            steps += 1;
+            pos += match direction {
+                Direction::Left => -1,
+                Direction::Right => 1,
+            };
+
+            if state == S::halt() {
+                break (direction, Some(HaltReason::Halt));
+            }
+            if pos < 0 || pos >= self.block_size as i64 {
+                break (direction, None);
+            }
+        };
+
+        Transition {
+            block: self.id(block),
+            state,
+            exit,
+            steps,
+            halt,
+        }
+    }
+
+    fn transition(&mut self, block: usize) -> Transition<S> {
+        let key = (self.state, self.edge, block);
+        if let Some(t) = self.transitions.get(&key) {
+            return t.clone();
+        }
+        let t = self.compute_transition(self.state, self.edge, block);
+        self.transitions.insert(key, t.clone());
+        t
+    }
+
+    /// Take the run of blocks the head is on the edge of off the tape, or a
+    /// single blank block past the end of it.
+    fn pop_run(&mut self) -> (usize, u64) {
+        let runs = match self.edge {
+            Direction::Left => &mut self.right,
+            Direction::Right => &mut self.left,
+        };
+        runs.pop().unwrap_or((0, 1))
+    }
+
+    fn write_tape<B: Write>(&self, output: &mut Option<B>) {
+        if let Some(b) = output {
+            let mut buffer = format!("{:8} {:?} {:?}  ", self.macro_steps, self.state, self.edge);
+            let head = match self.edge {
+                Direction::Left => self.left.len(),
+                Direction::Right => self.left.len().wrapping_sub(1),
+            };
+            let mut blocks = self
+                .left
+                .iter()
+                .chain(self.right.iter().rev())
+                .enumerate()
+                .map(|(idx, &(block, n))| {
+                    let cells = self.blocks[block]
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<String>();
+                    let run = format!("{}^{}", cells, n);
+                    if idx == head {
+                        format!("[{}]", run)
+                    } else {
+                        run
+                    }
+                })
+                .collect::<Vec<_>>();
+            // The head is on a blank block past the end of the tape.
+            match self.edge {
+                Direction::Left if self.right.is_empty() => blocks.push("[]".to_string()),
+                Direction::Right if self.left.is_empty() => blocks.insert(0, "[]".to_string()),
+                _ => (),
+            }
+            buffer.push_str(&blocks.join(" "));
+
+            writeln!(b, "{}", buffer).unwrap();
+        }
+    }
+
+    /// Run until the machine halts or has taken at least `limit` base
+    /// machine steps.
+    pub fn run_until_halt<B: Write>(&mut self, limit: usize, output: &mut Option<B>) {
+        while self.halt.is_none() {
+            self.write_tape(output);
+
+            if self.steps >= limit {
+                self.halt = Some(Halt::new(limit, HaltReason::XLimit));
+                break;
+            }
+
+            let edge = self.edge;
+            let (block, n) = self.pop_run();
+            let t = self.transition(block);
+
+            // Leaving on the far side in the same state, the head does the
+            // same for every block of the run.
+            let across = edge != t.exit && t.halt.is_none();
+            let count = if across && t.state == self.state {
+                n
+            } else {
+                1
+            };
+
+            let (behind, ahead) = match edge {
+                Direction::Left => (&mut self.left, &mut self.right),
+                Direction::Right => (&mut self.right, &mut self.left),
+            };
+            if count < n {
+                push_run(ahead, block, n - count);
+            }
+
+            self.macro_steps += count as usize;
+            self.steps += t.steps * count as usize;
+
+            match t.halt {
+                Some(HaltReason::Undefined(s)) => {
+                    push_run(ahead, t.block, 1);
+                    self.halt = Some(Halt::new(self.steps + 1, HaltReason::Undefined(s)));
+                    break;
+                }
+                Some(reason) => {
+                    push_run(ahead, t.block, 1);
+                    self.state = t.state;
+                    self.halt = Some(Halt::new(self.steps, reason));
+                    break;
+                }
+                None => (),
+            }
+
+            self.state = t.state;
+
+            if across {
+                push_run(behind, t.block, count);
+            } else {
+                // The head comes back out the side it went in, onto the edge
+                // of the block next to it.
+                push_run(ahead, t.block, 1);
+                self.edge = match edge {
+                    Direction::Left => Direction::Right,
+                    Direction::Right => Direction::Left,
+                };
+            }
+        }
+    }
+}
+
+/// Run the program as a macro machine with blocks of `block_size` cells,
+/// writing a summary, that starts with `prog_str` as it was given, to
+/// `output`.
+pub fn run_macro_machine<S: State + ToString, Sym: Symbol + ToString>(
+    program: Program<S, Sym>,
+    prog_str: &str,
+    block_size: usize,
+    limit: usize,
+    mut output: Option<Box<dyn Write>>,
+    verbose: bool,
+) -> MacroMachine<S, Sym> {
+    let mut machine = MacroMachine::new(program, block_size);
+
+    if verbose {
+        machine.run_until_halt(limit, &mut output);
+    } else {
+        machine.run_until_halt::<std::io::Stdout>(limit, &mut None);
+    }
+
+    if let Some(halt) = machine.halt() {
+        if let Some(w) = &mut output {
+            if let Err(e) = writeln!(
+                w,
+                "{}: marks {} steps {} reason {:?}",
+                prog_str,
+                machine.marks(),
+                halt.steps,
+                halt.reason
+            ) {
+                writeln!(std::io::stderr(), "Error writing: {}", e)
+                    .expect("Unable to write to stderr");
+            }
+        }
+    }
+    machine
+}
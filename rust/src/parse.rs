@@ -1,7 +1,12 @@
 use nom::{
-    character::{complete::satisfy, is_space},
-    combinator::recognize,
-    multi::separated_list1,
+    branch::alt,
+    bytes::complete::tag,
+    character::{
+        complete::{char, satisfy},
+        is_space,
+    },
+    combinator::{map, recognize},
+    multi::{many1, separated_list1},
     sequence::tuple,
     IResult,
 };
@@ -12,9 +17,20 @@ pub type Instructions = Vec<StateInstructions>;
 
 fn instruction(s: &str) -> IResult<&str, Instruction> {
     tuple((
-        satisfy(|c| matches!(c, '0'..='9') || c == '.'),
+        satisfy(|c| c.is_ascii_digit() || c == '.'),
         satisfy(|c| matches!(c, 'L' | 'R') || c == '.'),
-        satisfy(|c| matches!(c, 'A'..='Z') || c == '.'),
+        satisfy(|c| c.is_ascii_uppercase() || c == '.'),
+    ))(s)
+}
+
+fn compact_instruction(s: &str) -> IResult<&str, Instruction> {
+    alt((
+        map(tag("---"), |_| ('.', '.', '.')),
+        tuple((
+            satisfy(|c| c.is_ascii_digit()),
+            satisfy(|c| matches!(c, 'L' | 'R')),
+            satisfy(|c| c.is_ascii_uppercase()),
+        )),
     ))(s)
 }
 
@@ -34,6 +50,19 @@ pub fn parse_instructions(s: &str) -> IResult<&str, Instructions> {
     separated_list1(double, state_instructions)(s)
 }
 
+/// Parse the compact notation, eg `1RB1LB_1LA1RZ`, where each state's
+/// instructions are concatenated and states are separated by `_`.
+/// `---` is an undefined instruction.
+pub fn parse_compact_instructions(s: &str) -> IResult<&str, Instructions> {
+    separated_list1(char('_'), many1(compact_instruction))(s)
+}
+
+/// The compact notation has no whitespace, while the spaced notation
+/// always has at least one space between instructions.
+pub fn is_compact(s: &str) -> bool {
+    !s.contains(|c: char| is_space(c as u8))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +93,31 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_compact_instruction() {
+        assert_eq!(compact_instruction("1RZ"), Ok(("", ('1', 'R', 'Z'))));
+        assert_eq!(compact_instruction("---"), Ok(("", ('.', '.', '.'))));
+        assert!(compact_instruction("...").is_err());
+    }
+
+    #[test]
+    fn test_compact_instructions() {
+        assert_eq!(
+            parse_compact_instructions("1RB0LC_---1RZ"),
+            Ok((
+                "",
+                vec![
+                    vec![('1', 'R', 'B'), ('0', 'L', 'C')],
+                    vec![('.', '.', '.'), ('1', 'R', 'Z')]
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_is_compact() {
+        assert!(is_compact("1RB1LB_1LA1RZ"));
+        assert!(!is_compact("1RB 1LB  1LA 1RH"));
+    }
 }
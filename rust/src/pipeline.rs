@@ -0,0 +1,698 @@
+use crate::{
+    checkpoint::RunOptions,
+    decider::{
+        backward::{backward_reasoning, Backward},
+        bouncer::check_bouncer,
+        closed_language::find_closed_language,
+        cycler::check_cycler,
+        translated_cycler::check_translated_cycler,
+    },
+    machine::{Halt, HaltReason, Machine},
+    program::Program,
+    report::Report,
+    tape::Tape,
+    types::{State, Symbol},
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// How many steps back [`Decider::Backward`] reasons by name.
+pub const BACKWARD_DEPTH: usize = 30;
+
+/// How many states [`Decider::ClosedLanguage`]'s automata have at most by
+/// name.
+pub const CLOSED_LANGUAGE_STATES: usize = 2;
+
+/// A way of finding out how a machine ends up, that a [`Pipeline`] can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decider {
+    /// Run the machine until it halts.
+    Simulate,
+    /// See [`check_cycler`].
+    Cycler,
+    /// Lin and Rado's recurrence check, from step `start`, see
+    /// [`Machine::run_until_halt`]. With `blank`, it also stops when the
+    /// tape is blank from that step, in the same run.
+    Recurrence {
+        start: usize,
+        parallel: bool,
+        blank: Option<usize>,
+    },
+    /// Stop when the tape is blank, from step `start`.
+    Blanking { start: usize },
+    /// See [`check_translated_cycler`].
+    TranslatedCycler,
+    /// See [`check_bouncer`].
+    Bouncer,
+    /// See [`backward_reasoning`], reasoning back at most `depth` steps, or
+    /// the stage's step budget if that is fewer.
+    Backward { depth: usize },
+    /// See [`find_closed_language`], with automata of at most `states`
+    /// states, or the stage's step budget if that is fewer.
+    ClosedLanguage { states: usize },
+}
+
+impl Display for Decider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Decider::Simulate => "simulate",
+            Decider::Cycler => "cycler",
+            Decider::Recurrence {
+                parallel: false, ..
+            } => "recurrence",
+            Decider::Recurrence { parallel: true, .. } => "recurrence-parallel",
+            Decider::Blanking { .. } => "blanking",
+            Decider::TranslatedCycler => "translated-cycler",
+            Decider::Bouncer => "bouncer",
+            Decider::Backward { .. } => "backward",
+            Decider::ClosedLanguage { .. } => "closed-language",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Decider {
+    type Err = String;
+
+    /// A decider by name, with checks starting from the first step, reasoning
+    /// back [`BACKWARD_DEPTH`] steps and closed languages of automata with up
+    /// to [`CLOSED_LANGUAGE_STATES`] states.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simulate" => Ok(Decider::Simulate),
+            "cycler" => Ok(Decider::Cycler),
+            "recurrence" => Ok(Decider::Recurrence {
+                start: 0,
+                parallel: false,
+                blank: None,
+            }),
+            "recurrence-parallel" => Ok(Decider::Recurrence {
+                start: 0,
+                parallel: true,
+                blank: None,
+            }),
+            "blanking" => Ok(Decider::Blanking { start: 0 }),
+            "translated-cycler" => Ok(Decider::TranslatedCycler),
+            "bouncer" => Ok(Decider::Bouncer),
+            "backward" => Ok(Decider::Backward {
+                depth: BACKWARD_DEPTH,
+            }),
+            "closed-language" => Ok(Decider::ClosedLanguage {
+                states: CLOSED_LANGUAGE_STATES,
+            }),
+            a => Err(format!(
+                "Expected 'simulate', 'cycler', 'recurrence', 'recurrence-parallel', \
+                 'blanking', 'translated-cycler', 'bouncer', 'backward' or \
+                 'closed-language' got {}",
+                a
+            )),
+        }
+    }
+}
+
+impl Decider {
+    /// The decider for a stage given `steps` as its step budget, and the
+    /// budget to give it. The deciders that don't run the program take the
+    /// budget as how far to search, and keep their own if there isn't one,
+    /// rather than `limit`.
+    fn with_steps(self, steps: Option<usize>, limit: usize) -> (Self, usize) {
+        match (self, steps) {
+            (Decider::Backward { .. }, Some(depth)) => (Decider::Backward { depth }, depth),
+            (Decider::Backward { depth }, None) => (self, depth),
+            (Decider::ClosedLanguage { .. }, Some(states)) => {
+                (Decider::ClosedLanguage { states }, states)
+            }
+            (Decider::ClosedLanguage { states }, None) => (self, states),
+            (_, steps) => (self, steps.unwrap_or(limit)),
+        }
+    }
+
+    /// The recurrence check, blank check and whether to check in parallel,
+    /// for a decider that is [`Machine::run_until_halt`] with checks.
+    fn checks(self) -> Option<(Option<usize>, Option<usize>, bool)> {
+        match self {
+            Decider::Simulate => Some((None, None, false)),
+            Decider::Recurrence {
+                start,
+                parallel,
+                blank,
+            } => Some((Some(start), blank, parallel)),
+            Decider::Blanking { start } => Some((None, Some(start), false)),
+            Decider::Cycler
+            | Decider::TranslatedCycler
+            | Decider::Bouncer
+            | Decider::Backward { .. }
+            | Decider::ClosedLanguage { .. } => None,
+        }
+    }
+}
+
+/// How long a decider is given before it is counted as inconclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub steps: usize,
+    pub time: Option<Duration>,
+}
+
+impl Budget {
+    pub const fn steps(steps: usize) -> Self {
+        Self { steps, time: None }
+    }
+}
+
+/// A decider and its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stage {
+    pub decider: Decider,
+    pub budget: Budget,
+}
+
+/// Deciders to run one after the other, each on the program from the
+/// start, until one of them is conclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pipeline {
+    stages: Vec<Stage>,
+    accelerate: bool,
+}
+
+/// How a [`Pipeline`] decided a program.
+pub struct Verdict<S, Sym, T> {
+    /// The decider that reached a conclusion, or `None` if none did.
+    pub decider: Option<Decider>,
+    /// The machine the decider ran, or the one that got furthest if none
+    /// reached a conclusion.
+    pub machine: Machine<S, Sym, T>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a decider to run after the others, if they are inconclusive.
+    pub fn then(mut self, decider: Decider, budget: Budget) -> Self {
+        self.stages.push(Stage { decider, budget });
+        self
+    }
+
+    /// Sweep across runs of a symbol in one step when simulating, see
+    /// [`Machine::run_until_halt`].
+    pub fn accelerate(mut self, accelerate: bool) -> Self {
+        self.accelerate = accelerate;
+        self
+    }
+
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// The options for running the pipeline as one call to
+    /// [`Machine::run_until_halt`], if it is a single stage run that way with
+    /// no time budget. Such a run can be checkpointed and carried on later.
+    pub fn run_options(&self) -> Option<RunOptions> {
+        match self.stages.as_slice() {
+            [Stage {
+                decider,
+                budget: Budget { steps, time: None },
+            }] => {
+                let (check_recurrence, check_blank, parallel) = decider.checks()?;
+                Some(RunOptions {
+                    limit: *steps,
+                    check_recurrence,
+                    check_blank,
+                    parallel,
+                    accelerate: self.accelerate,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The pipeline for a single run with the blank and recurrence checks
+    /// given, which are made together if there are both.
+    pub fn from_checks(
+        limit: usize,
+        check_recurrence: Option<usize>,
+        check_blank: Option<usize>,
+        parallel: bool,
+    ) -> Self {
+        let decider = match (check_recurrence, check_blank) {
+            (Some(start), blank) => Decider::Recurrence {
+                start,
+                parallel,
+                blank,
+            },
+            (None, Some(start)) => Decider::Blanking { start },
+            (None, None) => Decider::Simulate,
+        };
+        Self::new().then(decider, Budget::steps(limit))
+    }
+
+    /// Parse a list of stages separated by commas, each a decider's name
+    /// followed by an optional step budget and time budget in seconds, eg
+    /// `cycler:1000,recurrence:100000:2.5`. Step budgets default to `limit`.
+    /// For `backward` and `closed-language` the step budget is the depth and
+    /// the most states, which default to [`BACKWARD_DEPTH`] and
+    /// [`CLOSED_LANGUAGE_STATES`].
+    pub fn parse(spec: &str, limit: usize) -> Result<Self, String> {
+        let mut pipeline = Self::new();
+        for stage in spec.split(',') {
+            let mut parts = stage.trim().split(':');
+            let decider: Decider = parts.next().unwrap_or_default().parse()?;
+            let steps = match parts.next() {
+                Some(steps) => Some(
+                    steps
+                        .parse()
+                        .map_err(|e| format!("Bad step budget {:?}: {}", steps, e))?,
+                ),
+                None => None,
+            };
+            let (decider, steps) = decider.with_steps(steps, limit);
+            let time = match parts.next() {
+                Some(time) => Some(
+                    time.parse()
+                        .ok()
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                        .ok_or_else(|| format!("Bad time budget {:?}", time))?,
+                ),
+                None => None,
+            };
+            if parts.next().is_some() {
+                return Err(format!("Too many budgets for {}", stage));
+            }
+            pipeline = pipeline.then(decider, Budget { steps, time });
+        }
+        Ok(pipeline)
+    }
+
+    /// Run each decider in turn on a fresh machine, until one is conclusive:
+    /// the machine halts, or it is proved never to.
+    pub fn run<
+        S: State + Send + Sync + ToString,
+        Sym: Symbol + Send + Sync + ToString,
+        T: Tape<Sym> + Send + Sync,
+    >(
+        &self,
+        prog: &Program<S, Sym>,
+    ) -> Verdict<S, Sym, T> {
+        let mut furthest: Option<Machine<S, Sym, T>> = None;
+
+        for &Stage { decider, budget } in &self.stages {
+            let mut machine = Machine::with_tape(prog.clone(), T::default());
+            let deadline = budget.time.map(|time| Instant::now() + time);
+            machine.set_deadline(deadline);
+
+            self.run_stage(&mut machine, decider, budget.steps, deadline);
+
+            machine.set_deadline(None);
+            if !machine.halt().is_some_and(Halt::is_limit) {
+                return Verdict {
+                    decider: Some(decider),
+                    machine,
+                };
+            }
+            if furthest
+                .as_ref()
+                .is_none_or(|m| machine.steps() > m.steps())
+            {
+                furthest = Some(machine);
+            }
+        }
+
+        let machine = furthest.unwrap_or_else(|| {
+            let mut machine = Machine::with_tape(prog.clone(), T::default());
+            machine.set_halt(Halt::new(0, HaltReason::XLimit));
+            machine
+        });
+
+        Verdict {
+            decider: None,
+            machine,
+        }
+    }
+
+    fn run_stage<
+        S: State + Send + Sync + ToString,
+        Sym: Symbol + Send + Sync + ToString,
+        T: Tape<Sym> + Send + Sync,
+    >(
+        &self,
+        machine: &mut Machine<S, Sym, T>,
+        decider: Decider,
+        limit: usize,
+        deadline: Option<Instant>,
+    ) {
+        let (check_recurrence, check_blank, parallel) = match decider {
+            Decider::Cycler => return check_cycler(machine, limit),
+            Decider::TranslatedCycler => return check_translated_cycler(machine, limit),
+            Decider::Bouncer => return check_bouncer(machine, limit),
+            Decider::Backward { depth } => {
+                let depth = depth.min(limit);
+                let reason = match backward_reasoning(machine.program(), depth, deadline) {
+                    Backward::NonHalting(proof) => HaltReason::Backward(proof.depth()),
+                    Backward::Unknown => HaltReason::XLimit,
+                };
+                return machine.set_halt(Halt::new(0, reason));
+            }
+            Decider::ClosedLanguage { states } => {
+                let states = states.min(limit);
+                let reason = match find_closed_language(machine.program(), states, deadline) {
+                    Some(certificate) => HaltReason::ClosedLanguage(certificate.states()),
+                    None => HaltReason::XLimit,
+                };
+                return machine.set_halt(Halt::new(0, reason));
+            }
+            _ => decider
+                .checks()
+                .expect("The other deciders make checks while simulating"),
+        };
+
+        machine.run_until_halt::<std::io::Stdout>(
+            vec![],
+            limit,
+            &mut None,
+            check_recurrence,
+            check_blank,
+            parallel,
+            self.accelerate,
+        );
+    }
+}
+
+impl<
+        S: State + Send + Sync + ToString,
+        Sym: Symbol + Send + Sync + ToString,
+        T: Tape<Sym> + Send + Sync,
+    > Verdict<S, Sym, T>
+{
+    pub fn halt(&self) -> &Halt {
+        self.machine
+            .halt()
+            .expect("A verdict's machine has stopped")
+    }
+
+    /// The report for the machine, saying which decider reached the verdict.
+    pub fn report(&self, runtime: Duration) -> Report {
+        let mut report = Report::new(&self.machine, runtime).expect("The machine has stopped");
+        report.decider = self.decider.map(|decider| decider.to_string());
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+    use crate::tape::{RleTape, VecTape};
+    use crate::types::{StateT, SymbolT};
+
+    type Verdict22 = Verdict<StateT<'B'>, SymbolT<'1'>, VecTape<SymbolT<'1'>>>;
+
+    fn run(pipeline: &Pipeline, prog: &str) -> Verdict22 {
+        match parse_program(prog).unwrap() {
+            ProgramT::TwoTwo(prog) => pipeline.run(&prog),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let pipeline = Pipeline::new()
+            .then(Decider::Cycler, Budget::steps(100))
+            .then(Decider::TranslatedCycler, Budget::steps(100))
+            .then(Decider::Bouncer, Budget::steps(1000));
+
+        let verdict = run(&pipeline, "1RB 1RB  1LA 1LA");
+        assert_eq!(verdict.decider, Some(Decider::Cycler));
+        assert_eq!(verdict.halt(), &Halt::new(2, HaltReason::Cycler(2)));
+
+        let verdict = run(&pipeline, "1RB 1LB  1LA 0RB");
+        assert_eq!(verdict.decider, Some(Decider::TranslatedCycler));
+
+        let verdict = run(&pipeline, "1RB 1LA  1LA 1RB");
+        assert_eq!(verdict.decider, Some(Decider::Bouncer));
+        assert_eq!(verdict.report(Duration::ZERO).decider.unwrap(), "bouncer");
+
+        let verdict = run(&pipeline, "1RB 1LB  1LA 1RH");
+        assert_eq!(verdict.decider, Some(Decider::Cycler));
+        assert_eq!(verdict.halt(), &Halt::new(6, HaltReason::Halt));
+    }
+
+    #[test]
+    fn test_pipeline_static() {
+        let pipeline = Pipeline::new()
+            .then(Decider::Simulate, Budget::steps(100))
+            .then(Decider::Backward { depth: 30 }, Budget::steps(100))
+            .then(Decider::ClosedLanguage { states: 2 }, Budget::steps(100));
+
+        // Never writes the 1 it would halt on.
+        let verdict = run(&pipeline, "0RB 1RH  0LA 0RB");
+        assert_eq!(verdict.decider, Some(Decider::Backward { depth: 30 }));
+        assert_eq!(verdict.halt(), &Halt::new(0, HaltReason::Backward(1)));
+        assert_eq!(verdict.report(Duration::ZERO).decider.unwrap(), "backward");
+
+        // Only ever reads blanks in A, but there are always more ways back.
+        let verdict = run(&pipeline, "1RB 1RH  0RA 0RA");
+        assert_eq!(verdict.decider, Some(Decider::ClosedLanguage { states: 2 }));
+        assert_eq!(verdict.halt(), &Halt::new(0, HaltReason::ClosedLanguage(2)));
+        assert_eq!(verdict.report(Duration::ZERO).reason, "ClosedLanguage");
+
+        // Halts after the simulation runs out.
+        let verdict = run(&pipeline, "1RB 1LB  1LA 1RH");
+        assert_eq!(verdict.decider, Some(Decider::Simulate));
+        let verdict = run(
+            &Pipeline::new()
+                .then(Decider::Backward { depth: 30 }, Budget::steps(100))
+                .then(Decider::ClosedLanguage { states: 2 }, Budget::steps(100)),
+            "1RB 1LB  1LA 1RH",
+        );
+        assert_eq!(verdict.decider, None);
+    }
+
+    #[test]
+    fn test_pipeline_static_budgets() {
+        assert_eq!(
+            Pipeline::parse("backward:5:0.1,closed-language:3", 10)
+                .unwrap()
+                .stages(),
+            &[
+                Stage {
+                    decider: Decider::Backward { depth: 5 },
+                    budget: Budget {
+                        steps: 5,
+                        time: Some(Duration::from_millis(100))
+                    }
+                },
+                Stage {
+                    decider: Decider::ClosedLanguage { states: 3 },
+                    budget: Budget::steps(3)
+                }
+            ]
+        );
+
+        // Needs automata of two states, and backward reasoning of one step.
+        let verdict = run(
+            &Pipeline::parse("closed-language:1", 10).unwrap(),
+            "1RB 1RH  0RA 0RA",
+        );
+        assert_eq!(verdict.decider, None);
+        let verdict = run(
+            &Pipeline::new().then(Decider::Backward { depth: 30 }, Budget::steps(1)),
+            "0RB 1RH  0LA 0RB",
+        );
+        assert_eq!(verdict.decider, None);
+
+        // Out of time before the first pair of automata is tried.
+        let verdict = run(
+            &Pipeline::new().then(
+                Decider::ClosedLanguage { states: 2 },
+                Budget {
+                    steps: 2,
+                    time: Some(Duration::ZERO),
+                },
+            ),
+            "1RB 1RH  0RA 0RA",
+        );
+        assert_eq!(verdict.decider, None);
+    }
+
+    #[test]
+    fn test_pipeline_inconclusive() {
+        let pipeline = Pipeline::new()
+            .then(Decider::Cycler, Budget::steps(10))
+            .then(Decider::Simulate, Budget::steps(50))
+            .then(Decider::TranslatedCycler, Budget::steps(20));
+
+        let verdict = run(&pipeline, "1RB 1LA  1LA 1RB");
+        assert_eq!(verdict.decider, None);
+        assert_eq!(verdict.halt(), &Halt::new(50, HaltReason::XLimit));
+
+        let verdict = run(&Pipeline::new(), "1RB 1LA  1LA 1RB");
+        assert_eq!(verdict.halt(), &Halt::new(0, HaltReason::XLimit));
+    }
+
+    #[test]
+    fn test_pipeline_time() {
+        let pipeline = Pipeline::new().then(
+            Decider::Simulate,
+            Budget {
+                steps: usize::MAX - 1,
+                time: Some(Duration::from_millis(10)),
+            },
+        );
+
+        let verdict = run(&pipeline, "1RB 1LA  1LA 1RB");
+        assert_eq!(verdict.decider, None);
+        assert!(verdict.halt().is_limit());
+    }
+
+    #[test]
+    fn test_pipeline_time_accelerated() {
+        // Sweeps back and forth over a growing run of 1s, so nearly every
+        // step is taken as part of a chain, and none of the other steps land
+        // on a multiple of the steps between looks at the clock. The time is
+        // up straight away, so it stops as soon as the clock is looked at.
+        let pipeline = Pipeline::new().accelerate(true).then(
+            Decider::Simulate,
+            Budget {
+                steps: 1_000_000,
+                time: Some(Duration::ZERO),
+            },
+        );
+
+        let verdict: Verdict<_, _, RleTape<_>> = match parse_program("0RB 1LA  1LA 1RB").unwrap() {
+            ProgramT::TwoTwo(prog) => pipeline.run(&prog),
+            _ => unreachable!(),
+        };
+        assert_eq!(verdict.decider, None);
+        assert!(verdict.halt().is_limit());
+        assert!(verdict.halt().steps < 5000);
+    }
+
+    #[test]
+    fn test_pipeline_from_checks() {
+        assert_eq!(
+            Pipeline::from_checks(100, Some(5), Some(0), true).stages(),
+            &[Stage {
+                decider: Decider::Recurrence {
+                    start: 5,
+                    parallel: true,
+                    blank: Some(0)
+                },
+                budget: Budget::steps(100)
+            }]
+        );
+        assert_eq!(
+            Pipeline::from_checks(100, None, Some(0), false),
+            Pipeline::new().then(Decider::Blanking { start: 0 }, Budget::steps(100))
+        );
+        assert_eq!(
+            Pipeline::from_checks(100, None, None, false),
+            Pipeline::new().then(Decider::Simulate, Budget::steps(100))
+        );
+
+        // Blanks before it recurs, and recurs without blanking.
+        let pipeline = Pipeline::from_checks(1000, Some(0), Some(0), false);
+        let verdict = run(&pipeline, "1RB 0RA  1LB 1LA");
+        assert_eq!(verdict.halt().reason, HaltReason::Blanking);
+        let verdict = run(&pipeline, "1RB 1LB  1LA 0RB");
+        assert!(matches!(verdict.halt().reason, HaltReason::Recurr(_)));
+    }
+
+    #[test]
+    fn test_pipeline_run_options() {
+        assert_eq!(
+            Pipeline::from_checks(100, Some(5), Some(0), true)
+                .accelerate(true)
+                .run_options(),
+            Some(RunOptions {
+                limit: 100,
+                check_recurrence: Some(5),
+                check_blank: Some(0),
+                parallel: true,
+                accelerate: true,
+            })
+        );
+        assert_eq!(Pipeline::parse("cycler", 100).unwrap().run_options(), None);
+        assert_eq!(
+            Pipeline::parse("simulate:100:1", 100)
+                .unwrap()
+                .run_options(),
+            None
+        );
+        assert_eq!(
+            Pipeline::parse("simulate,simulate", 100)
+                .unwrap()
+                .run_options(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pipeline_parse() {
+        assert_eq!(
+            Pipeline::parse("cycler:1000, recurrence:50:2.5,bouncer", 10),
+            Ok(Pipeline::new()
+                .then(Decider::Cycler, Budget::steps(1000))
+                .then(
+                    Decider::Recurrence {
+                        start: 0,
+                        parallel: false,
+                        blank: None
+                    },
+                    Budget {
+                        steps: 50,
+                        time: Some(Duration::from_millis(2500))
+                    }
+                )
+                .then(Decider::Bouncer, Budget::steps(10)))
+        );
+
+        assert_eq!(
+            Pipeline::parse("recurrence-parallel", 10).map(|p| p.stages()[0].decider),
+            Ok(Decider::Recurrence {
+                start: 0,
+                parallel: true,
+                blank: None
+            })
+        );
+        assert_eq!(
+            Decider::Recurrence {
+                start: 0,
+                parallel: true,
+                blank: None
+            }
+            .to_string(),
+            "recurrence-parallel"
+        );
+
+        assert_eq!(
+            Pipeline::parse("backward,closed-language", 10)
+                .unwrap()
+                .stages()
+                .iter()
+                .map(|stage| stage.decider)
+                .collect::<Vec<_>>(),
+            [
+                Decider::Backward {
+                    depth: BACKWARD_DEPTH
+                },
+                Decider::ClosedLanguage {
+                    states: CLOSED_LANGUAGE_STATES
+                }
+            ]
+        );
+        assert_eq!(
+            Decider::ClosedLanguage { states: 3 }.to_string(),
+            "closed-language"
+        );
+
+        assert!(Pipeline::parse("cycle", 10).is_err());
+        assert!(Pipeline::parse("cycler:lots", 10).is_err());
+        assert!(Pipeline::parse("cycler:10:-1", 10).is_err());
+        assert!(Pipeline::parse("cycler:10:1:1", 10).is_err());
+    }
+}
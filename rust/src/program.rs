@@ -1,18 +1,169 @@
 use crate::{
-    parse::parse_instructions,
-    types::{Direction, State, StateT, Symbol, SymbolT},
+    parse::{is_compact, parse_compact_instructions, parse_instructions, Instructions},
+    types::{Direction, DynState, DynSymbol, State, StateT, Symbol, SymbolT},
+};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    fmt::Display,
 };
-use std::{collections::BTreeMap, convert::TryFrom};
 
 pub type Action<S, Sym> = (S, Sym);
 pub type Instruction<S, Sym> = (S, Sym, Direction);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Program<State, Symbol>(
     BTreeMap<Action<State, Symbol>, Option<Instruction<State, Symbol>>>,
 );
 
 impl<S: State, Sym: Symbol> Program<S, Sym> {
+    /// A program with every instruction undefined.
+    pub fn undefined() -> Self {
+        Self::undefined_with_size(S::states().len(), Sym::symbols().len())
+    }
+
+    /// A program with every instruction undefined, using only the first
+    /// `states` states and `symbols` symbols.
+    pub fn undefined_with_size(states: usize, symbols: usize) -> Self {
+        let mut inner = BTreeMap::default();
+        for state in S::states().into_iter().take(states) {
+            for symbol in Sym::symbols().into_iter().take(symbols) {
+                inner.insert((state, symbol), None);
+            }
+        }
+        Program(inner)
+    }
+
+    pub fn set_instruction(
+        &mut self,
+        state: S,
+        symbol: Sym,
+        instruction: Option<Instruction<S, Sym>>,
+    ) {
+        self.0.insert((state, symbol), instruction);
+    }
+
+    /// Iterate over every state, symbol pair and its instruction, in order.
+    pub fn instructions(
+        &self,
+    ) -> impl Iterator<Item = (&Action<S, Sym>, &Option<Instruction<S, Sym>>)> + '_ {
+        self.0.iter()
+    }
+
+    /// The states that have instructions, in order.
+    pub fn states(&self) -> Vec<S> {
+        self.0.keys().map(|&(state, _)| state).dedup().collect()
+    }
+
+    /// The symbols that each state has instructions for, in order.
+    pub fn symbols(&self) -> Vec<Sym> {
+        self.0
+            .keys()
+            .map(|&(_, symbol)| symbol)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// The same program with every Left and Right swapped.
+    pub fn mirror(&self) -> Self {
+        Program(
+            self.0
+                .iter()
+                .map(|(&action, instr)| {
+                    let instr = instr.map(|(st, sym, dir)| {
+                        let dir = match dir {
+                            Direction::Left => Direction::Right,
+                            Direction::Right => Direction::Left,
+                        };
+                        (st, sym, dir)
+                    });
+                    (action, instr)
+                })
+                .collect(),
+        )
+    }
+
+    /// A canonical representative of the programs that differ from this one
+    /// only by naming of states, mirroring or the direction of a halting
+    /// instruction.
+    ///
+    /// States are renamed in the order they are first used when reading the
+    /// program table from the initial state, states that are never used keep
+    /// their relative order after those. Halting instructions always move
+    /// right. With `mirror`, every direction is swapped if the first
+    /// non-halting instruction, in table order, moves left.
+    pub fn normalize(&self, mirror: bool) -> Self {
+        let states = self.states();
+        let symbols = self.symbols();
+
+        let mut order = vec![S::initial_state()];
+        let mut idx = 0;
+        while idx < order.len() {
+            let state = order[idx];
+            for &symbol in &symbols {
+                if let Some(Some((st, _, _))) = self.0.get(&(state, symbol)) {
+                    if *st != S::halt() && !order.contains(st) && states.contains(st) {
+                        order.push(*st);
+                    }
+                }
+            }
+            idx += 1;
+        }
+        for &state in &states {
+            if !order.contains(&state) {
+                order.push(state);
+            }
+        }
+
+        let rename = |state: S| -> S {
+            order
+                .iter()
+                .position(|s| *s == state)
+                .map_or(state, |i| states[i])
+        };
+
+        let normalized = Program(
+            self.0
+                .iter()
+                .map(|(&(state, symbol), instr)| {
+                    let instr = instr.map(|(st, sym, dir)| {
+                        if st == S::halt() {
+                            (st, sym, Direction::Right)
+                        } else {
+                            (rename(st), sym, dir)
+                        }
+                    });
+                    ((rename(state), symbol), instr)
+                })
+                .collect::<BTreeMap<_, _>>(),
+        );
+
+        let first_left = normalized
+            .0
+            .values()
+            .flatten()
+            .find(|(st, _, _)| *st != S::halt())
+            .is_some_and(|(_, _, dir)| *dir == Direction::Left);
+
+        if mirror && first_left {
+            let mut mirrored = normalized.mirror();
+            for instr in mirrored.0.values_mut().flatten() {
+                if instr.0 == S::halt() {
+                    instr.2 = Direction::Right;
+                }
+            }
+            mirrored
+        } else {
+            normalized
+        }
+    }
+
+    pub fn is_normalized(&self, mirror: bool) -> bool {
+        self.normalize(mirror) == *self
+    }
+
     pub fn instruction(&self, state: S, symbol: Sym) -> Option<&Instruction<S, Sym>> {
         self.0
             .get(&(state, symbol))
@@ -21,12 +172,114 @@ impl<S: State, Sym: Symbol> Program<S, Sym> {
             )
             .as_ref()
     }
+
+    /// Write the program in the given notation. Parsing the result with
+    /// `parse_program` gives back the same program.
+    pub fn to_notation(&self, notation: Notation) -> String {
+        let (state_sep, instr_sep, undefined, halt) = match notation {
+            Notation::Spaced => ("  ", " ", "...", 'H'),
+            Notation::Compact => ("_", "", "---", 'Z'),
+        };
+        // With 8 or more states `H` is taken, so halting is always `Z`.
+        let halt = if self.states().iter().any(|s| s.to_string() == "H") {
+            'Z'
+        } else {
+            halt
+        };
+
+        let mut out = String::new();
+        let mut current = None;
+
+        for (&(state, _), instr) in self.0.iter() {
+            match current {
+                None => (),
+                Some(s) if s == state => out.push_str(instr_sep),
+                Some(_) => out.push_str(state_sep),
+            }
+            current = Some(state);
+
+            match instr {
+                Some((st, sym, dir)) => {
+                    out.push_str(&sym.to_string());
+                    out.push_str(&dir.to_string());
+                    if *st == S::halt() {
+                        out.push(halt);
+                    } else {
+                        out.push_str(&st.to_string());
+                    }
+                }
+                None => out.push_str(undefined),
+            }
+        }
+        out
+    }
+
+    /// The program as a Graphviz DOT graph, with a node for each state and
+    /// an edge labelled `read/write,direction` for each instruction.
+    /// Halting instructions go to a `halt` node, and undefined ones are
+    /// dashed edges to an `undefined` node.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n    node [shape=circle];\n");
+
+        for state in self.states() {
+            out.push_str(&format!("    \"{}\";\n", state));
+        }
+
+        let mut halts = false;
+        let mut undefined = false;
+        for ((state, read), instr) in self.0.iter() {
+            match instr {
+                Some((st, sym, dir)) if *st == S::halt() => {
+                    halts = true;
+                    out.push_str(&format!(
+                        "    \"{}\" -> halt [label=\"{}/{},{}\", color=blue, penwidth=2];\n",
+                        state, read, sym, dir
+                    ));
+                }
+                Some((st, sym, dir)) => out.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"{}/{},{}\"];\n",
+                    state, st, read, sym, dir
+                )),
+                None => {
+                    undefined = true;
+                    out.push_str(&format!(
+                        "    \"{}\" -> undefined [label=\"{}\", color=red, style=dashed];\n",
+                        state, read
+                    ));
+                }
+            }
+        }
+
+        if halts {
+            out.push_str("    halt [shape=doublecircle, color=blue];\n");
+        }
+        if undefined {
+            out.push_str("    undefined [shape=box, color=red, style=dashed, label=\"?\"];\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl<S: State, Sym: Symbol> Display for Program<S, Sym> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_notation(Notation::Spaced))
+    }
+}
+
+/// The textual dialects a program can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    /// `1RB 1LB  1LA 1RH`
+    Spaced,
+    /// `1RB1LB_1LA1RZ`
+    Compact,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct ProgramParseError(pub String);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProgramT {
     TwoTwo(Program<StateT<'B'>, SymbolT<'1'>>),
     TwoThree(Program<StateT<'B'>, SymbolT<'2'>>),
@@ -43,10 +296,130 @@ pub enum ProgramT {
     SixTwo(Program<StateT<'F'>, SymbolT<'1'>>),
     SixThree(Program<StateT<'F'>, SymbolT<'2'>>),
     SixFour(Program<StateT<'F'>, SymbolT<'3'>>),
+    /// Any other complexity, up to 25 states and 10 colors.
+    Dynamic(Program<DynState, DynSymbol>),
+}
+
+impl ProgramT {
+    pub fn to_notation(&self, notation: Notation) -> String {
+        match self {
+            ProgramT::TwoTwo(p) => p.to_notation(notation),
+            ProgramT::TwoThree(p) => p.to_notation(notation),
+            ProgramT::TwoFour(p) => p.to_notation(notation),
+            ProgramT::ThreeTwo(p) => p.to_notation(notation),
+            ProgramT::ThreeThree(p) => p.to_notation(notation),
+            ProgramT::ThreeFour(p) => p.to_notation(notation),
+            ProgramT::FourTwo(p) => p.to_notation(notation),
+            ProgramT::FourThree(p) => p.to_notation(notation),
+            ProgramT::FourFour(p) => p.to_notation(notation),
+            ProgramT::FiveTwo(p) => p.to_notation(notation),
+            ProgramT::FiveThree(p) => p.to_notation(notation),
+            ProgramT::FiveFour(p) => p.to_notation(notation),
+            ProgramT::SixTwo(p) => p.to_notation(notation),
+            ProgramT::SixThree(p) => p.to_notation(notation),
+            ProgramT::SixFour(p) => p.to_notation(notation),
+            ProgramT::Dynamic(p) => p.to_notation(notation),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        match self {
+            ProgramT::TwoTwo(p) => p.to_dot(),
+            ProgramT::TwoThree(p) => p.to_dot(),
+            ProgramT::TwoFour(p) => p.to_dot(),
+            ProgramT::ThreeTwo(p) => p.to_dot(),
+            ProgramT::ThreeThree(p) => p.to_dot(),
+            ProgramT::ThreeFour(p) => p.to_dot(),
+            ProgramT::FourTwo(p) => p.to_dot(),
+            ProgramT::FourThree(p) => p.to_dot(),
+            ProgramT::FourFour(p) => p.to_dot(),
+            ProgramT::FiveTwo(p) => p.to_dot(),
+            ProgramT::FiveThree(p) => p.to_dot(),
+            ProgramT::FiveFour(p) => p.to_dot(),
+            ProgramT::SixTwo(p) => p.to_dot(),
+            ProgramT::SixThree(p) => p.to_dot(),
+            ProgramT::SixFour(p) => p.to_dot(),
+            ProgramT::Dynamic(p) => p.to_dot(),
+        }
+    }
+}
+
+impl ProgramT {
+    pub fn normalize(&self, mirror: bool) -> Self {
+        match self {
+            ProgramT::TwoTwo(p) => ProgramT::TwoTwo(p.normalize(mirror)),
+            ProgramT::TwoThree(p) => ProgramT::TwoThree(p.normalize(mirror)),
+            ProgramT::TwoFour(p) => ProgramT::TwoFour(p.normalize(mirror)),
+            ProgramT::ThreeTwo(p) => ProgramT::ThreeTwo(p.normalize(mirror)),
+            ProgramT::ThreeThree(p) => ProgramT::ThreeThree(p.normalize(mirror)),
+            ProgramT::ThreeFour(p) => ProgramT::ThreeFour(p.normalize(mirror)),
+            ProgramT::FourTwo(p) => ProgramT::FourTwo(p.normalize(mirror)),
+            ProgramT::FourThree(p) => ProgramT::FourThree(p.normalize(mirror)),
+            ProgramT::FourFour(p) => ProgramT::FourFour(p.normalize(mirror)),
+            ProgramT::FiveTwo(p) => ProgramT::FiveTwo(p.normalize(mirror)),
+            ProgramT::FiveThree(p) => ProgramT::FiveThree(p.normalize(mirror)),
+            ProgramT::FiveFour(p) => ProgramT::FiveFour(p.normalize(mirror)),
+            ProgramT::SixTwo(p) => ProgramT::SixTwo(p.normalize(mirror)),
+            ProgramT::SixThree(p) => ProgramT::SixThree(p.normalize(mirror)),
+            ProgramT::SixFour(p) => ProgramT::SixFour(p.normalize(mirror)),
+            ProgramT::Dynamic(p) => ProgramT::Dynamic(p.normalize(mirror)),
+        }
+    }
+
+    pub fn is_normalized(&self, mirror: bool) -> bool {
+        self.normalize(mirror) == *self
+    }
 }
 
+impl Display for ProgramT {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_notation(Notation::Spaced))
+    }
+}
+
+/// Parse either the spaced notation, `1RB 1LB  1LA 1RH`, or the compact
+/// notation, `1RB1LB_1LA1RZ`, into a program of the matching complexity.
+/// Complexities without a fixed type are parsed into `ProgramT::Dynamic`.
 pub fn parse_program(prog_str: &str) -> Result<ProgramT, ProgramParseError> {
-    let (rest, instructions) = match parse_instructions(prog_str) {
+    let instructions = parse_table(prog_str)?;
+    let state = instructions.len();
+    let color = instructions[0].len();
+
+    match (state, color) {
+        (2, 2) => construct_program(instructions, ProgramT::TwoTwo),
+        (2, 3) => construct_program(instructions, ProgramT::TwoThree),
+        (2, 4) => construct_program(instructions, ProgramT::TwoFour),
+        (3, 2) => construct_program(instructions, ProgramT::ThreeTwo),
+        (3, 3) => construct_program(instructions, ProgramT::ThreeThree),
+        (3, 4) => construct_program(instructions, ProgramT::ThreeFour),
+        (4, 2) => construct_program(instructions, ProgramT::FourTwo),
+        (4, 3) => construct_program(instructions, ProgramT::FourThree),
+        (4, 4) => construct_program(instructions, ProgramT::FourFour),
+        (5, 2) => construct_program(instructions, ProgramT::FiveTwo),
+        (5, 3) => construct_program(instructions, ProgramT::FiveThree),
+        (5, 4) => construct_program(instructions, ProgramT::FiveFour),
+        (6, 2) => construct_program(instructions, ProgramT::SixTwo),
+        (6, 3) => construct_program(instructions, ProgramT::SixThree),
+        (6, 4) => construct_program(instructions, ProgramT::SixFour),
+        _ => construct_dynamic_program(instructions).map(ProgramT::Dynamic),
+    }
+}
+
+/// Parse a program of any supported complexity, including the ones that
+/// have a fixed type, into the dynamic representation.
+pub fn parse_dynamic_program(
+    prog_str: &str,
+) -> Result<Program<DynState, DynSymbol>, ProgramParseError> {
+    construct_dynamic_program(parse_table(prog_str)?)
+}
+
+fn parse_table(prog_str: &str) -> Result<Instructions, ProgramParseError> {
+    let parsed = if is_compact(prog_str) {
+        parse_compact_instructions(prog_str)
+    } else {
+        parse_instructions(prog_str)
+    };
+    let (rest, instructions) = match parsed {
         Ok(inner) => inner,
         Err(e) => {
             return Err(ProgramParseError(format!(
@@ -74,29 +447,7 @@ pub fn parse_program(prog_str: &str) -> Result<ProgramT, ProgramParseError> {
         )));
     }
 
-    let state = instructions.len();
-
-    match (state, color) {
-        (2, 2) => construct_program(instructions, ProgramT::TwoTwo),
-        (2, 3) => construct_program(instructions, ProgramT::TwoThree),
-        (2, 4) => construct_program(instructions, ProgramT::TwoFour),
-        (3, 2) => construct_program(instructions, ProgramT::ThreeTwo),
-        (3, 3) => construct_program(instructions, ProgramT::ThreeThree),
-        (3, 4) => construct_program(instructions, ProgramT::ThreeFour),
-        (4, 2) => construct_program(instructions, ProgramT::FourTwo),
-        (4, 3) => construct_program(instructions, ProgramT::FourThree),
-        (4, 4) => construct_program(instructions, ProgramT::FourFour),
-        (5, 2) => construct_program(instructions, ProgramT::FiveTwo),
-        (5, 3) => construct_program(instructions, ProgramT::FiveThree),
-        (5, 4) => construct_program(instructions, ProgramT::FiveFour),
-        (6, 2) => construct_program(instructions, ProgramT::SixTwo),
-        (6, 3) => construct_program(instructions, ProgramT::SixThree),
-        (6, 4) => construct_program(instructions, ProgramT::SixFour),
-        (s, c) => Err(ProgramParseError(format!(
-            "State must be (2-6) inclusive, color must be (2-4) inclusive not state={}, color={}",
-            s, c
-        ))),
-    }
+    Ok(instructions)
 }
 
 fn construct_program<
@@ -104,17 +455,17 @@ fn construct_program<
     const S: char,
     const C: char,
 >(
-    instructions: Vec<Vec<(char, char, char)>>,
+    instructions: Instructions,
     func: F,
 ) -> Result<ProgramT, ProgramParseError> {
     let mut inner = BTreeMap::default();
     for ((state, symbol), (sym, dir, st)) in instructions
         .into_iter()
-        .zip(<StateT<S> as State>::states().into_iter())
+        .zip(<StateT<S> as State>::states())
         .flat_map(|(instr, state)| {
             instr
                 .into_iter()
-                .zip(<SymbolT<C> as Symbol>::symbols().into_iter())
+                .zip(<SymbolT<C> as Symbol>::symbols())
                 .map(move |(i, sym)| ((state, sym), i))
         })
     {
@@ -131,6 +482,61 @@ fn construct_program<
     Ok(func(Program(inner)))
 }
 
+fn construct_dynamic_program(
+    instructions: Instructions,
+) -> Result<Program<DynState, DynSymbol>, ProgramParseError> {
+    let (state, color) = (instructions.len(), instructions[0].len());
+
+    if !(2..=DynState::MAX).contains(&state) || !(2..=DynSymbol::MAX).contains(&color) {
+        return Err(ProgramParseError(format!(
+            "State must be (2-{}) inclusive, color must be (2-{}) inclusive not state={}, color={}",
+            DynState::MAX,
+            DynSymbol::MAX,
+            state,
+            color
+        )));
+    }
+
+    let mut inner = BTreeMap::default();
+    for (i, instrs) in instructions.into_iter().enumerate() {
+        for (j, (sym, dir, st)) in instrs.into_iter().enumerate() {
+            let action = (DynState::Val(i as u8), DynSymbol(j as u8));
+            if sym == '.' && st == '.' && dir == '.' {
+                inner.insert(action, None);
+                continue;
+            }
+
+            let sym = DynSymbol::try_from(sym)?;
+            // `H` is the halt state unless there are enough states to name one `H`.
+            let st = match st {
+                'H' if state <= 7 => DynState::Halt,
+                st => DynState::try_from(st)?,
+            };
+            let dir = Direction::try_from(dir)?;
+
+            if sym.0 as usize >= color {
+                return Err(ProgramParseError(format!(
+                    "Expected Symbol Instruction '0' to '{}' found {}",
+                    color - 1,
+                    sym
+                )));
+            }
+            if let DynState::Val(s) = st {
+                if s as usize >= state {
+                    return Err(ProgramParseError(format!(
+                        "Expected State instruction 'A' to {} or halt got {}",
+                        DynState::Val(state as u8 - 1),
+                        st
+                    )));
+                }
+            }
+
+            inner.insert(action, Some((st, sym, dir)));
+        }
+    }
+    Ok(Program(inner))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -182,4 +588,209 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_program_parse_compact() {
+        let program = parse_program("1RB1LC_1RC1RZ_1LA0LB");
+
+        assert!(program.is_ok());
+
+        let prog = program.unwrap();
+
+        assert!(matches!(prog, ProgramT::ThreeTwo(_)));
+        if let ProgramT::ThreeTwo(prog) = prog {
+            assert_eq!(
+                prog.instruction(StateT::<'C'>::Val('B'), SymbolT::<'1'>('1')),
+                Some(&(StateT::<'C'>::Halt, SymbolT::<'1'>('1'), Direction::Right))
+            );
+        }
+    }
+
+    #[test]
+    fn test_program_compact_undefined() {
+        let program = parse_program("1RB---_1LA1RZ");
+
+        assert!(program.is_ok());
+
+        let prog = program.unwrap();
+
+        assert!(matches!(prog, ProgramT::TwoTwo(_)));
+        if let ProgramT::TwoTwo(prog) = prog {
+            assert_eq!(
+                prog.instruction(StateT::<'B'>::Val('A'), SymbolT::<'1'>('1')),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_program_display() {
+        let prog = parse_program("1RB ...  1LA 1RH").unwrap();
+
+        assert_eq!(prog.to_string(), "1RB ...  1LA 1RH");
+        assert_eq!(prog.to_notation(Notation::Compact), "1RB---_1LA1RZ");
+    }
+
+    #[test]
+    fn test_program_round_trip() {
+        for prog_str in &[
+            "1RB 2LB 1RH  2LA 2RB 1LB",
+            "1RB 1LC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA",
+            "1RB 2LA 1RA 1RA  1LB 1LA 3RB 1RH",
+            "1RB 1LC 1RH  1LA 1LC 2RB  1RB 2LC 1RC",
+            "1RB 0LC  1LC 1RB  1RB ...  0LD 0LC  ... 0RA  1RF 1RD",
+        ] {
+            let prog = parse_program(prog_str).unwrap();
+            let spaced = prog.to_notation(Notation::Spaced);
+            let compact = prog.to_notation(Notation::Compact);
+
+            assert_eq!(&spaced, prog_str);
+            assert_eq!(parse_program(&spaced).unwrap(), prog);
+            assert_eq!(parse_program(&compact).unwrap(), prog);
+            assert_eq!(
+                parse_program(&compact)
+                    .unwrap()
+                    .to_notation(Notation::Compact),
+                compact
+            );
+        }
+    }
+
+    #[test]
+    fn test_program_parse_dynamic() {
+        let prog = parse_program("1RB 2LA 1RA 4RB 1RH  1LB 1LA 3RB 0LA 2LB").unwrap();
+
+        assert!(matches!(prog, ProgramT::Dynamic(_)));
+        if let ProgramT::Dynamic(prog) = prog {
+            assert_eq!(prog.states(), vec![DynState::Val(0), DynState::Val(1)]);
+            assert_eq!(prog.symbols().len(), 5);
+            assert_eq!(
+                prog.instruction(DynState::Val(0), DynSymbol(4)),
+                Some(&(DynState::Halt, DynSymbol(1), Direction::Right))
+            );
+        }
+
+        // With 8 states `H` is a state, and only `Z` halts.
+        let prog =
+            parse_program("1RH1RZ_------_------_------_------_------_------_1LA---").unwrap();
+
+        if let ProgramT::Dynamic(prog) = &prog {
+            assert_eq!(
+                prog.instruction(DynState::Val(0), DynSymbol(0)),
+                Some(&(DynState::Val(7), DynSymbol(1), Direction::Right))
+            );
+            assert_eq!(
+                prog.instruction(DynState::Val(0), DynSymbol(1)),
+                Some(&(DynState::Halt, DynSymbol(1), Direction::Right))
+            );
+        }
+        assert_eq!(
+            prog.to_string(),
+            "1RH 1RZ  ... ...  ... ...  ... ...  ... ...  ... ...  ... ...  1LA ..."
+        );
+        assert_eq!(parse_program(&prog.to_string()).unwrap(), prog);
+    }
+
+    #[test]
+    fn test_program_parse_dynamic_errors() {
+        // A state past the last one
+        assert!(
+            parse_program("1RB 1LH  1LA 1RC  1RA 1RA  1RA 1RA  1RA 1RA  1RA 1RA  1RI 1RA").is_err()
+        );
+        // A symbol past the last one
+        assert!(parse_program("1RB 5LA 1RA 1RA 1RA  1LB 1LA 3RB 1RH 1RA").is_err());
+        // Too many colors
+        assert!(parse_program(&["1RA"; 11].join(" ")).is_err());
+        // Too many states
+        assert!(parse_program(&["1RA 1RA"; 26].join("  ")).is_err());
+    }
+
+    #[test]
+    fn test_program_dynamic_matches_fixed() {
+        for prog_str in &[
+            "1RB 2LB 1RH  2LA 2RB 1LB",
+            "1RB 1LC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA",
+            "1RB 0LC  1LC 1RB  1RB ...  0LD 0LC  ... 0RA  1RF 1RD",
+        ] {
+            let prog = parse_program(prog_str).unwrap();
+            let dynamic = parse_dynamic_program(prog_str).unwrap();
+
+            assert_eq!(dynamic.to_string(), prog.to_string());
+            assert_eq!(
+                dynamic.normalize(true).to_string(),
+                prog.normalize(true).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_program_to_dot() {
+        if let ProgramT::TwoTwo(prog) = parse_program("1RB ...  1LA 1RH").unwrap() {
+            assert_eq!(
+                prog.to_dot(),
+                "digraph {
+    node [shape=circle];
+    \"A\";
+    \"B\";
+    \"A\" -> \"B\" [label=\"0/1,R\"];
+    \"A\" -> undefined [label=\"1\", color=red, style=dashed];
+    \"B\" -> \"A\" [label=\"0/1,L\"];
+    \"B\" -> halt [label=\"1/1,R\", color=blue, penwidth=2];
+    halt [shape=doublecircle, color=blue];
+    undefined [shape=box, color=red, style=dashed, label=\"?\"];
+}
+"
+            );
+        }
+    }
+
+    #[test]
+    fn test_program_normalize_mirror() {
+        let prog = parse_program("1LB 1RB  1RA 1RH").unwrap();
+
+        assert!(!prog.is_normalized(true));
+        assert!(prog.is_normalized(false));
+        assert_eq!(prog.normalize(true).to_string(), "1RB 1LB  1LA 1RH");
+    }
+
+    #[test]
+    fn test_program_normalize_states() {
+        let prog = parse_program("1RC 1LC  1LB ...  1LA 1RB").unwrap();
+
+        assert_eq!(
+            prog.normalize(true).to_string(),
+            "1RB 1LB  1LA 1RC  1LC ..."
+        );
+    }
+
+    #[test]
+    fn test_program_normalize_halt() {
+        let prog = parse_program("1RB 0LH  1LA 1RB").unwrap();
+
+        assert_eq!(prog.normalize(false).to_string(), "1RB 0RH  1LA 1RB");
+    }
+
+    #[test]
+    fn test_program_normalize_idempotent() {
+        for prog_str in &[
+            "1RB 2LB 1RH  2LA 2RB 1LB",
+            "1LB 1RC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA",
+            "1LB 2RA 1LA 1LA  1RB 1RA 3LB 1LH",
+            "0RB 0LA  1RC 0RH  0LC 1RD  0LD 1RE  1LA 0LE",
+            "1RB 0LC  1LC 1RB  1RB ...  0LD 0LC  ... 0RA  1RF 1RD",
+        ] {
+            let prog = parse_program(prog_str).unwrap();
+            let normalized = prog.normalize(true);
+
+            assert!(normalized.is_normalized(true));
+            assert_eq!(normalized.normalize(true), normalized);
+        }
+    }
+
+    #[test]
+    fn test_program_compact_mismatched_colors() {
+        let program = parse_program("1RB1LC_1RC_1LA0LB");
+
+        assert!(program.is_err());
+    }
 }
@@ -0,0 +1,401 @@
+use crate::{
+    machine::{HaltReason, Machine},
+    tape::Tape,
+    types::{State, Symbol},
+};
+use serde::Serialize;
+use std::{collections::BTreeMap, io::Write, str::FromStr, time::Duration};
+
+/// The formats results can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The summary line, `{program}: marks {} steps {} reason {:?}`.
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// Comma separated values, after a header line.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            a => Err(format!("Expected 'text', 'json' or 'csv' got {}", a)),
+        }
+    }
+}
+
+const CSV_HEADER: &[&str] = &[
+    "program",
+    "marks",
+    "steps",
+    "reason",
+    "period",
+    "start",
+    "offset",
+    "words",
+    "undefined",
+    "decider",
+    "leftmost",
+    "rightmost",
+    "cells",
+    "head",
+    "head_leftmost",
+    "head_rightmost",
+    "visits",
+    "runtime",
+    "error",
+];
+
+/// How a machine stopped, for consumption by other tools.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub program: String,
+    pub marks: usize,
+    pub steps: usize,
+    /// The name of the [`HaltReason`], eg `Halt` or `Recurr`.
+    pub reason: String,
+    /// The period of a recurrence, quasihalt or translated cycler, the steps
+    /// between a bouncer's first sweeps, how far back a backward reasoning
+    /// proof goes, or the most states of a closed tape language's automata.
+    pub period: Option<usize>,
+    /// The step a recurrence, quasihalt, translated cycler or bouncer starts
+    /// on.
+    pub start: Option<usize>,
+    /// How far a translated cycler moves along the tape each period.
+    pub offset: Option<i64>,
+    /// The words a bouncer's tape grows by each sweep.
+    pub words: Option<Vec<String>>,
+    /// The state and symbol with no instruction, eg `B1`.
+    pub undefined: Option<String>,
+    /// The decider that stopped the machine, when it was run by a
+    /// [`Pipeline`](crate::pipeline::Pipeline).
+    pub decider: Option<String>,
+    /// The leftmost cell written or visited by the head.
+    pub leftmost: i64,
+    /// The rightmost cell written or visited by the head.
+    pub rightmost: i64,
+    /// The number of cells the head has been over.
+    pub cells: usize,
+    /// Where the head finished, relative to where it started.
+    pub head: i64,
+    pub head_leftmost: i64,
+    pub head_rightmost: i64,
+    /// The number of steps taken from each state.
+    pub visits: BTreeMap<String, usize>,
+    /// Seconds taken to run the machine.
+    pub runtime: f64,
+}
+
+impl Report {
+    /// The report for a machine that has stopped, or `None` if it is still
+    /// running.
+    pub fn new<S: State + Send + Sync, Sym: Symbol + Send + Sync, T: Tape<Sym> + Send + Sync>(
+        machine: &Machine<S, Sym, T>,
+        runtime: Duration,
+    ) -> Option<Self> {
+        let halt = machine.halt()?;
+
+        let (reason, period, start, offset, words, undefined) = match &halt.reason {
+            HaltReason::Halt => ("Halt", None, None, None, None, None),
+            HaltReason::Recurr(p) => ("Recurr", Some(*p), Some(halt.steps), None, None, None),
+            HaltReason::XLimit => ("XLimit", None, None, None, None, None),
+            HaltReason::Quasihalt(p) => ("Quasihalt", Some(*p), Some(halt.steps), None, None, None),
+            HaltReason::Blanking => ("Blanking", None, None, None, None, None),
+            HaltReason::Undefined(a) => ("Undefined", None, None, None, None, Some(a.clone())),
+            HaltReason::TranslatedCycler(p, o) => (
+                "TranslatedCycler",
+                Some(*p),
+                Some(halt.steps),
+                Some(*o),
+                None,
+                None,
+            ),
+            HaltReason::Bouncer(p, w) => (
+                "Bouncer",
+                Some(*p),
+                Some(halt.steps),
+                None,
+                Some(w.clone()),
+                None,
+            ),
+            HaltReason::Cycler(p) => ("Cycler", Some(*p), Some(halt.steps), None, None, None),
+            HaltReason::Backward(d) => ("Backward", Some(*d), None, None, None, None),
+            HaltReason::ClosedLanguage(s) => ("ClosedLanguage", Some(*s), None, None, None, None),
+        };
+
+        let head = machine.head();
+        let space = machine.space();
+
+        Some(Self {
+            program: machine.program().to_string(),
+            marks: machine.marks(),
+            steps: halt.steps,
+            reason: reason.to_string(),
+            period,
+            start,
+            offset,
+            words,
+            undefined,
+            decider: None,
+            leftmost: machine.tape().leftmost().min(head),
+            rightmost: (machine.tape().rightmost() - 1).max(head),
+            cells: space.cells(),
+            head,
+            head_leftmost: space.leftmost,
+            head_rightmost: space.rightmost,
+            visits: space
+                .visits
+                .iter()
+                .map(|(s, n)| (s.to_string(), *n))
+                .collect(),
+            runtime: runtime.as_secs_f64(),
+        })
+    }
+
+    pub fn write<B: Write + ?Sized>(&self, w: &mut B, format: Format) -> std::io::Result<()> {
+        match format {
+            Format::Text => {
+                let reason = match (&self.period, &self.offset, &self.words, &self.undefined) {
+                    (Some(p), Some(o), _, _) => format!("{}({}, {})", self.reason, p, o),
+                    (Some(p), _, Some(w), _) => format!("{}({}, {:?})", self.reason, p, w),
+                    (Some(p), ..) => format!("{}({})", self.reason, p),
+                    (.., Some(a)) => format!("{}({:?})", self.reason, a),
+                    _ => self.reason.clone(),
+                };
+                writeln!(
+                    w,
+                    "{}: marks {} steps {} reason {}",
+                    self.program, self.marks, self.steps, reason
+                )
+            }
+            Format::Json => writeln!(w, "{}", to_json(self)),
+            Format::Csv => write_csv(
+                w,
+                &[
+                    self.program.clone(),
+                    self.marks.to_string(),
+                    self.steps.to_string(),
+                    self.reason.clone(),
+                    self.period.map_or_else(String::new, |p| p.to_string()),
+                    self.start.map_or_else(String::new, |s| s.to_string()),
+                    self.offset.map_or_else(String::new, |o| o.to_string()),
+                    self.words
+                        .as_ref()
+                        .map_or_else(String::new, |w| w.join(" ")),
+                    self.undefined.clone().unwrap_or_default(),
+                    self.decider.clone().unwrap_or_default(),
+                    self.leftmost.to_string(),
+                    self.rightmost.to_string(),
+                    self.cells.to_string(),
+                    self.head.to_string(),
+                    self.head_leftmost.to_string(),
+                    self.head_rightmost.to_string(),
+                    self.visits_string(),
+                    self.runtime.to_string(),
+                    String::new(),
+                ],
+            ),
+        }
+    }
+}
+
+impl Report {
+    /// The steps taken from each state, eg `A:3 B:3`.
+    pub fn visits_string(&self) -> String {
+        self.visits
+            .iter()
+            .map(|(s, n)| format!("{}:{}", s, n))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Write a line of the space statistics, to go after the text summary.
+    pub fn write_space<B: Write + ?Sized>(&self, w: &mut B) -> std::io::Result<()> {
+        writeln!(
+            w,
+            "cells {} head {} leftmost {} rightmost {} visits {}",
+            self.cells,
+            self.head,
+            self.head_leftmost,
+            self.head_rightmost,
+            self.visits_string()
+        )
+    }
+}
+
+/// Write the line that comes before any results, if the format has one.
+pub fn write_header<B: Write + ?Sized>(w: &mut B, format: Format) -> std::io::Result<()> {
+    match format {
+        Format::Csv => writeln!(w, "{}", CSV_HEADER.join(",")),
+        Format::Text | Format::Json => Ok(()),
+    }
+}
+
+/// Write the result for a program that couldn't be run, with the reason
+/// `Error`.
+pub fn write_error<B: Write + ?Sized>(
+    w: &mut B,
+    format: Format,
+    program: &str,
+    error: &str,
+) -> std::io::Result<()> {
+    match format {
+        Format::Text => writeln!(w, "{}: error {}", program, error),
+        Format::Json => writeln!(
+            w,
+            "{}",
+            serde_json::json!({ "program": program, "reason": "Error", "error": error })
+        ),
+        Format::Csv => {
+            let mut fields = vec![String::new(); CSV_HEADER.len()];
+            fields[0] = program.to_string();
+            fields[3] = "Error".to_string();
+            fields[CSV_HEADER.len() - 1] = error.to_string();
+            write_csv(w, &fields)
+        }
+    }
+}
+
+fn to_json(report: &Report) -> String {
+    serde_json::to_string(report).expect("Reports are always serializable")
+}
+
+fn write_csv<B: Write + ?Sized>(w: &mut B, fields: &[String]) -> std::io::Result<()> {
+    let line = fields
+        .iter()
+        .map(|f| {
+            if f.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(w, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::{parse_program, ProgramT};
+
+    fn report(prog: &str, limit: usize, check: Option<usize>) -> Report {
+        if let ProgramT::TwoTwo(prog) = parse_program(prog).unwrap() {
+            let mut machine = Machine::new(prog);
+            machine.run_until_halt::<std::io::Stdout>(
+                vec![],
+                limit,
+                &mut None,
+                check,
+                None,
+                false,
+                false,
+            );
+            Report::new(&machine, Duration::from_millis(1500)).unwrap()
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn written(report: &Report, format: Format) -> String {
+        let mut out = vec![];
+        report.write(&mut out, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_report_halt() {
+        let report = report("1RB 1LB  1LA 1RH", 100, None);
+
+        assert_eq!(report.reason, "Halt");
+        assert_eq!((report.marks, report.steps), (4, 6));
+        assert_eq!((report.leftmost, report.rightmost), (-2, 1));
+        assert_eq!(
+            written(&report, Format::Text),
+            "1RB 1LB  1LA 1RH: marks 4 steps 6 reason Halt\n"
+        );
+        assert_eq!(
+            written(&report, Format::Csv),
+            "1RB 1LB  1LA 1RH,4,6,Halt,,,,,,,-2,1,4,0,-2,1,A:3 B:3,1.5,\n"
+        );
+        assert_eq!(
+            written(&report, Format::Json),
+            "{\"program\":\"1RB 1LB  1LA 1RH\",\"marks\":4,\"steps\":6,\"reason\":\"Halt\",\
+             \"period\":null,\"start\":null,\"offset\":null,\"words\":null,\"undefined\":null,\"decider\":null,\"leftmost\":-2,\"rightmost\":1,\
+             \"cells\":4,\"head\":0,\"head_leftmost\":-2,\"head_rightmost\":1,\
+             \"visits\":{\"A\":3,\"B\":3},\"runtime\":1.5}\n"
+        );
+
+        let mut space = vec![];
+        report.write_space(&mut space).unwrap();
+
+        assert_eq!(
+            String::from_utf8(space).unwrap(),
+            "cells 4 head 0 leftmost -2 rightmost 1 visits A:3 B:3\n"
+        );
+    }
+
+    #[test]
+    fn test_report_recurrence() {
+        let report = report("1RB 1LB  1LA 0RB", 1000, Some(100));
+
+        assert_eq!(report.reason, "Recurr");
+        assert_eq!(report.period, Some(3));
+        assert_eq!(report.start, Some(report.steps));
+        assert!(written(&report, Format::Text).ends_with("reason Recurr(3)\n"));
+    }
+
+    #[test]
+    fn test_report_proved() {
+        use crate::machine::Halt;
+
+        if let ProgramT::TwoTwo(prog) = parse_program("0RB 1RH  0LA 0RB").unwrap() {
+            for (reason, name, n) in [
+                (HaltReason::Backward(1), "Backward", 1),
+                (HaltReason::ClosedLanguage(2), "ClosedLanguage", 2),
+            ] {
+                let mut machine = Machine::new(prog.clone());
+                machine.set_halt(Halt::new(0, reason));
+                let report = Report::new(&machine, Duration::ZERO).unwrap();
+
+                assert_eq!((report.reason.as_str(), report.period), (name, Some(n)));
+                assert!(
+                    written(&report, Format::Text).ends_with(&format!("reason {}({})\n", name, n))
+                );
+                assert!(written(&report, Format::Csv)
+                    .starts_with(&format!("0RB 1RH  0LA 0RB,0,0,{},{},,", name, n)));
+                assert!(written(&report, Format::Json)
+                    .contains(&format!("\"reason\":\"{}\",\"period\":{},", name, n)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_report_undefined() {
+        let report = report("1RB 1LB  1LA ...", 100, None);
+
+        assert_eq!(report.undefined, Some("B1".to_string()));
+        assert!(written(&report, Format::Text).ends_with("reason Undefined(\"B1\")\n"));
+    }
+
+    #[test]
+    fn test_report_error() {
+        let mut out = vec![];
+        write_header(&mut out, Format::Csv).unwrap();
+        write_error(&mut out, Format::Csv, "1RB", "bad, \"very\" bad").unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "program,marks,steps,reason,period,start,offset,words,undefined,decider,leftmost,rightmost,cells,\
+             head,head_leftmost,head_rightmost,visits,runtime,error\n\
+             1RB,,,Error,,,,,,,,,,,,,,,\"bad, \"\"very\"\" bad\"\n"
+        );
+    }
+}
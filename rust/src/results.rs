@@ -0,0 +1,285 @@
+use crate::{program::parse_program, report::Report};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+/// The reason of programs that were neither found to halt nor proved not to.
+const HOLDOUT: &str = "XLimit";
+
+#[derive(Debug, PartialEq)]
+pub struct ResultsError(pub String);
+
+/// The verdict for one program, as kept in a [`ResultsDb`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// The program, normalized so that programs that only differ by the
+    /// names of their states, the directions of their halting instructions
+    /// or by being mirrored are the same, see [`Program::normalize`]. Symbols
+    /// are not renamed, so programs that only differ by those are not.
+    ///
+    /// [`Program::normalize`]: crate::program::Program::normalize
+    pub program: String,
+    /// The name of the [`HaltReason`](crate::machine::HaltReason), eg `Halt`.
+    pub reason: String,
+    pub steps: usize,
+    pub marks: usize,
+    /// The decider that reached the verdict, if it is known.
+    pub decider: Option<String>,
+    /// How the program was run, eg the deciders and their budgets.
+    pub parameters: Option<String>,
+}
+
+impl Record {
+    /// A record of a report, with the program normalized.
+    pub fn new(report: &Report, parameters: Option<String>) -> Result<Self, ResultsError> {
+        Ok(Self {
+            program: normalize(&report.program)?,
+            reason: report.reason.clone(),
+            steps: report.steps,
+            marks: report.marks,
+            decider: report.decider.clone(),
+            parameters,
+        })
+    }
+
+    /// A record of a line written by `turing batch --format json`, or `None`
+    /// if the line is for a program that couldn't be run.
+    pub fn parse(line: &str, parameters: Option<String>) -> Result<Option<Self>, ResultsError> {
+        let value: serde_json::Value =
+            serde_json::from_str(line).map_err(|e| ResultsError(e.to_string()))?;
+        if value["reason"] == "Error" {
+            return Ok(None);
+        }
+
+        let record: Self =
+            serde_json::from_value(value).map_err(|e| ResultsError(e.to_string()))?;
+        Ok(Some(Self {
+            program: normalize(&record.program)?,
+            parameters,
+            ..record
+        }))
+    }
+
+    /// Whether the program was neither found to halt nor proved not to.
+    pub fn is_holdout(&self) -> bool {
+        self.reason == HOLDOUT
+    }
+}
+
+/// The normalized form of a program, that it is kept under in a
+/// [`ResultsDb`].
+pub fn normalize(program: &str) -> Result<String, ResultsError> {
+    parse_program(program)
+        .map(|program| program.normalize(true).to_string())
+        .map_err(|e| ResultsError(format!("Error parsing {}: {}", program, e.0)))
+}
+
+/// Results kept in a file with one JSON [`Record`] per line, which is only
+/// ever appended to. When a program has been recorded more than once, the
+/// last record is the one that counts.
+///
+/// The file is never loaded whole: inserting only appends to it, and reading
+/// streams it, keeping no more than each program's latest line and reason.
+pub struct ResultsDb {
+    path: PathBuf,
+}
+
+impl ResultsDb {
+    /// The results in a file, which is created when something is first
+    /// inserted if it doesn't exist.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Add records to the end of the file, replacing any earlier ones for
+    /// the same programs. Returns how many were added.
+    pub fn insert<I: IntoIterator<Item = Record>>(
+        &self,
+        records: I,
+    ) -> Result<usize, ResultsError> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .map_err(|e| ResultsError(e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+
+        let mut inserted = 0;
+        for record in records {
+            let json = serde_json::to_string(&record).expect("Records are always serializable");
+            writeln!(writer, "{}", json).map_err(|e| ResultsError(e.to_string()))?;
+            inserted += 1;
+        }
+
+        writer.flush().map_err(|e| ResultsError(e.to_string()))?;
+        Ok(inserted)
+    }
+
+    /// The record for a program, in any of its forms.
+    pub fn get(&self, program: &str) -> Result<Option<Record>, ResultsError> {
+        let program = normalize(program)?;
+        let mut found = None;
+        for line in self.lines()? {
+            let (_, record) = line?;
+            if record.program == program {
+                found = Some(record);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Every program's latest record, in the order they were inserted, or
+    /// only those with the reason given, eg `Halt` or `Recurr`.
+    pub fn records(
+        &self,
+        reason: Option<&str>,
+    ) -> Result<impl Iterator<Item = Result<Record, ResultsError>>, ResultsError> {
+        let latest = self
+            .latest()?
+            .into_values()
+            .filter(|(_, latest)| reason.is_none_or(|reason| latest == reason))
+            .map(|(idx, _)| idx)
+            .collect::<HashSet<_>>();
+
+        Ok(self
+            .lines()?
+            .filter(move |line| match line {
+                Ok((idx, _)) => latest.contains(idx),
+                Err(_) => true,
+            })
+            .map(|line| line.map(|(_, record)| record)))
+    }
+
+    /// The programs that are still undecided, in order of program.
+    pub fn holdouts(&self) -> Result<impl Iterator<Item = String>, ResultsError> {
+        Ok(self
+            .latest()?
+            .into_iter()
+            .filter(|(_, (_, reason))| reason == HOLDOUT)
+            .map(|(program, _)| program))
+    }
+
+    /// The line of each program's latest record, and its reason.
+    fn latest(&self) -> Result<BTreeMap<String, (usize, String)>, ResultsError> {
+        let mut latest = BTreeMap::new();
+        for line in self.lines()? {
+            let (idx, record) = line?;
+            latest.insert(record.program, (idx, record.reason));
+        }
+        Ok(latest)
+    }
+
+    /// The records in the file and the lines they are on, read as they are
+    /// needed.
+    fn lines(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<(usize, Record), ResultsError>>, ResultsError> {
+        let file = match File::open(&self.path) {
+            Ok(file) => Some(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(ResultsError(e.to_string())),
+        };
+
+        Ok(file
+            .into_iter()
+            .flat_map(|file| BufReader::new(file).lines().enumerate())
+            .filter_map(|(idx, line)| match line {
+                Ok(line) if line.trim().is_empty() => None,
+                Ok(line) => Some(
+                    serde_json::from_str(&line)
+                        .map(|record| (idx, record))
+                        .map_err(|e| ResultsError(format!("Line {}: {}", idx + 1, e))),
+                ),
+                Err(e) => Some(Err(ResultsError(e.to_string()))),
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(program: &str, reason: &str, steps: usize) -> Record {
+        Record {
+            program: normalize(program).unwrap(),
+            reason: reason.to_string(),
+            steps,
+            marks: 0,
+            decider: Some("simulate".to_string()),
+            parameters: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("1LB 1RB  1RA 1LH").unwrap(),
+            normalize("1RB 1LB  1LA 1RH").unwrap()
+        );
+        assert!(normalize("1RB").is_err());
+    }
+
+    #[test]
+    fn test_record_parse() {
+        let line = r#"{"program":"1LB 1RB  1RA 1LH","marks":4,"steps":6,"reason":"Halt","decider":"simulate","runtime":0.1}"#;
+        assert_eq!(
+            Record::parse(line, Some("limit=100".to_string())).unwrap(),
+            Some(Record {
+                marks: 4,
+                parameters: Some("limit=100".to_string()),
+                ..record("1RB 1LB  1LA 1RH", "Halt", 6)
+            })
+        );
+
+        let error = r#"{"program":"bad","reason":"Error","error":"Error parsing"}"#;
+        assert_eq!(Record::parse(error, None).unwrap(), None);
+        assert!(Record::parse("{}", None).is_err());
+    }
+
+    #[test]
+    fn test_results_db() {
+        let path = std::env::temp_dir().join(format!("results-{}.jsonl", std::process::id()));
+
+        let db = ResultsDb::new(&path);
+        assert_eq!(db.records(None).unwrap().count(), 0);
+
+        db.insert(vec![
+            record("1RB 1LB  1LA 1RH", "Halt", 6),
+            record("1RB 1LA  1LA 1RB", "XLimit", 100),
+            record("1RB 1LB  1LA 0RB", "XLimit", 100),
+        ])
+        .unwrap();
+        assert_eq!(
+            db.insert(vec![record("1LB 1RB  1RA 0LB", "Recurr", 7)])
+                .unwrap(),
+            1
+        );
+
+        let db = ResultsDb::new(&path);
+        let records = db
+            .records(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let recurrences = db.records(Some("Recurr")).unwrap().count();
+        let holdouts = db.holdouts().unwrap().collect::<Vec<_>>();
+        let halt = db.get("1RB 1LB  1LA 1RH").unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].reason, "Recurr");
+        assert_eq!(halt.steps, 6);
+        assert_eq!(recurrences, 1);
+        assert_eq!(holdouts, vec![normalize("1RB 1LA  1LA 1RB").unwrap()]);
+    }
+}
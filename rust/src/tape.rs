@@ -1,60 +1,512 @@
-use crate::types::Symbol;
+use crate::types::{Direction, Symbol};
+use std::iter::{repeat, repeat_n};
 
+/// A tape that is infinite in both directions, addressed by signed position.
+/// Cells that have never been written read as `Symbol::zero`.
+pub trait Tape<Sym: Symbol>: Default + Clone {
+    fn read(&self, pos: i64) -> Sym;
+
+    fn write(&mut self, pos: i64, symbol: Sym);
+
+    /// The position of the leftmost cell that has been written.
+    fn leftmost(&self) -> i64;
+
+    /// One past the position of the rightmost cell that has been written.
+    fn rightmost(&self) -> i64;
+
+    fn iter_between(&self, first: i64, last: i64) -> impl Iterator<Item = Sym> + '_;
+
+    fn iter(&self) -> impl Iterator<Item = Sym> + '_ {
+        self.iter_between(self.leftmost(), self.rightmost())
+    }
+
+    fn size(&self) -> usize {
+        (self.rightmost() - self.leftmost()) as usize
+    }
+
+    /// The number of cells that aren't `Symbol::zero`.
+    fn marks(&self) -> usize {
+        self.iter().filter(|s| *s != Sym::zero()).count()
+    }
+
+    /// The number of cells, up to `max`, from `pos` going in `direction`
+    /// that hold the same symbol as `pos`. A run of `Symbol::zero` that
+    /// reaches past the written cells is endless, so is `max` long.
+    fn run_length(&self, pos: i64, direction: Direction, max: u64) -> u64 {
+        let symbol = self.read(pos);
+        let mut count = 0;
+        let mut pos = pos;
+        while count < max {
+            let past = match direction {
+                Direction::Left => pos < self.leftmost(),
+                Direction::Right => pos >= self.rightmost(),
+            };
+            if past {
+                return if symbol == Sym::zero() { max } else { count };
+            }
+            if self.read(pos) != symbol {
+                return count;
+            }
+            count += 1;
+            pos += match direction {
+                Direction::Left => -1,
+                Direction::Right => 1,
+            };
+        }
+        max
+    }
+
+    /// Write `symbol` to `count` cells from `pos` going in `direction`.
+    fn fill(&mut self, pos: i64, direction: Direction, count: u64, symbol: Sym) {
+        for i in 0..count as i64 {
+            match direction {
+                Direction::Left => self.write(pos - i, symbol),
+                Direction::Right => self.write(pos + i, symbol),
+            }
+        }
+    }
+}
+
+/// Cells at and to the right of position 0 are stored in `right`, cells to
+/// the left of position 0 are stored in `left` with position -1 first, so
+/// growing in either direction is an amortized O(1) push.
 #[derive(Debug, Clone)]
-pub struct Tape<Symbol>(Vec<Symbol>);
+pub struct VecTape<Symbol> {
+    left: Vec<Symbol>,
+    right: Vec<Symbol>,
+}
 
-impl<Sym: Symbol> Default for Tape<Sym> {
+impl<Sym: Symbol> Default for VecTape<Sym> {
     fn default() -> Self {
-        Self((0..1).map(|_| Sym::zero()).collect::<Vec<_>>())
+        Self {
+            left: vec![],
+            right: vec![Sym::zero()],
+        }
     }
 }
 
-impl<Sym: Symbol> Tape<Sym> {
-    pub fn read(&self, pos: usize) -> Option<&Sym> {
-        self.0.get(pos)
+impl<Sym: Symbol> Tape<Sym> for VecTape<Sym> {
+    fn read(&self, pos: i64) -> Sym {
+        let cell = if pos < 0 {
+            self.left.get((-pos - 1) as usize)
+        } else {
+            self.right.get(pos as usize)
+        };
+        cell.copied().unwrap_or_else(Sym::zero)
+    }
+
+    fn write(&mut self, pos: i64, symbol: Sym) {
+        let (side, idx) = if pos < 0 {
+            (&mut self.left, (-pos - 1) as usize)
+        } else {
+            (&mut self.right, pos as usize)
+        };
+        if idx >= side.len() {
+            side.resize(idx + 1, Sym::zero());
+        }
+        side[idx] = symbol;
+    }
+
+    fn leftmost(&self) -> i64 {
+        -(self.left.len() as i64)
+    }
+
+    fn rightmost(&self) -> i64 {
+        self.right.len() as i64
     }
 
-    pub fn insert(&mut self) {
-        self.0.insert(0, Sym::zero());
+    fn iter_between(&self, first: i64, last: i64) -> impl Iterator<Item = Sym> + '_ {
+        (first..last).map(move |i| self.read(i))
     }
 
-    pub fn write(&mut self, pos: usize, symbol: Sym) {
-        if let Some(val) = self.0.get_mut(pos) {
-            *val = symbol;
+    fn iter(&self) -> impl Iterator<Item = Sym> + '_ {
+        self.left.iter().rev().chain(self.right.iter()).copied()
+    }
+
+    fn size(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+}
+
+/// A run-length encoded tape, for machines whose tapes are mostly long blocks
+/// of the same symbol.
+///
+/// The runs are kept on two stacks either side of a cursor, which follows
+/// the last write. Reads and writes next to the cursor are O(1), since that
+/// is where the head of a machine is.
+#[derive(Debug, Clone)]
+pub struct RleTape<Symbol> {
+    /// Runs to the left of the cursor, the last one ends next to it.
+    left: Vec<(Symbol, u64)>,
+    /// Runs at and to the right of the cursor, the last one starts at it.
+    right: Vec<(Symbol, u64)>,
+    cursor: i64,
+    leftmost: i64,
+    rightmost: i64,
+}
+
+impl<Sym: Symbol> Default for RleTape<Sym> {
+    fn default() -> Self {
+        Self {
+            left: vec![],
+            right: vec![],
+            cursor: 0,
+            leftmost: 0,
+            rightmost: 1,
+        }
+    }
+}
+
+fn push_run<Sym: Symbol>(runs: &mut Vec<(Sym, u64)>, symbol: Sym, count: u64) {
+    match runs.last_mut() {
+        Some((s, n)) if *s == symbol => *n += count,
+        _ => runs.push((symbol, count)),
+    }
+}
+
+/// Move up to `count` cells from the run at the top of `from` to `to`,
+/// returning how many were moved. An empty `from` is an endless run of
+/// `Symbol::zero`.
+fn move_cells<Sym: Symbol>(
+    from: &mut Vec<(Sym, u64)>,
+    to: &mut Vec<(Sym, u64)>,
+    count: u64,
+) -> u64 {
+    match from.last_mut() {
+        Some((s, n)) => {
+            let symbol = *s;
+            let moved = count.min(*n);
+            *n -= moved;
+            if *n == 0 {
+                from.pop();
+            }
+            push_run(to, symbol, moved);
+            moved
+        }
+        None => {
+            push_run(to, Sym::zero(), count);
+            count
+        }
+    }
+}
+
+impl<Sym: Symbol> RleTape<Sym> {
+    fn move_to(&mut self, pos: i64) {
+        while self.cursor < pos {
+            self.cursor +=
+                move_cells(&mut self.right, &mut self.left, (pos - self.cursor) as u64) as i64;
+        }
+        while self.cursor > pos {
+            self.cursor -=
+                move_cells(&mut self.left, &mut self.right, (self.cursor - pos) as u64) as i64;
+        }
+    }
+
+    /// The runs of symbols, from the left, and the position the first starts at.
+    fn runs(&self) -> (i64, impl Iterator<Item = &(Sym, u64)> + '_) {
+        let start = self.cursor - self.left.iter().map(|(_, n)| *n as i64).sum::<i64>();
+        (start, self.left.iter().chain(self.right.iter().rev()))
+    }
+
+    /// The number of runs the tape is stored as.
+    pub fn runs_len(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+}
+
+impl<Sym: Symbol> Tape<Sym> for RleTape<Sym> {
+    fn read(&self, pos: i64) -> Sym {
+        let (runs, mut offset) = if pos >= self.cursor {
+            (&self.right, (pos - self.cursor) as u64)
         } else {
-            self.0.push(symbol);
+            (&self.left, (self.cursor - pos - 1) as u64)
+        };
+        for &(symbol, n) in runs.iter().rev() {
+            if offset < n {
+                return symbol;
+            }
+            offset -= n;
         }
+        Sym::zero()
     }
 
-    pub fn iter_between(&'_ self, first: i64, last: i64) -> impl Iterator<Item = Sym> + '_ {
-        (first..last).map(move |i| {
-            if i < 0 {
-                Sym::zero()
-            } else {
-                self.0.get(i as usize).cloned().unwrap_or_else(Sym::zero)
+    fn write(&mut self, pos: i64, symbol: Sym) {
+        self.move_to(pos);
+        match self.right.last_mut() {
+            Some((s, _)) if *s == symbol => (),
+            Some((_, n)) => {
+                *n -= 1;
+                if *n == 0 {
+                    self.right.pop();
+                }
+                push_run(&mut self.right, symbol, 1);
             }
-        })
+            None => self.right.push((symbol, 1)),
+        }
+        self.leftmost = self.leftmost.min(pos);
+        self.rightmost = self.rightmost.max(pos + 1);
+    }
+
+    fn leftmost(&self) -> i64 {
+        self.leftmost
+    }
+
+    fn rightmost(&self) -> i64 {
+        self.rightmost
+    }
+
+    fn iter_between(&self, first: i64, last: i64) -> impl Iterator<Item = Sym> + '_ {
+        let (start, runs) = self.runs();
+        let mut pos = start;
+        let cells = runs
+            .filter_map(move |&(symbol, n)| {
+                let (from, to) = (pos.max(first), (pos + n as i64).min(last));
+                pos += n as i64;
+                if from < to {
+                    Some(repeat_n(symbol, (to - from) as usize))
+                } else {
+                    None
+                }
+            })
+            .flatten();
+        let before = (start - first).clamp(0, (last - first).max(0)) as usize;
+
+        repeat_n(Sym::zero(), before)
+            .chain(cells)
+            .chain(repeat(Sym::zero()))
+            .take((last - first).max(0) as usize)
     }
 
-    pub fn iter_to(&'_ self, to: usize) -> impl Iterator<Item = Sym> + '_ {
-        (0..to).map(move |i| self.0.get(i).cloned().unwrap_or_else(Sym::zero))
+    fn run_length(&self, pos: i64, direction: Direction, max: u64) -> u64 {
+        let symbol = self.read(pos);
+
+        // The runs in `direction` from the cursor, and the runs the other way.
+        let (ahead, behind, offset) = match direction {
+            Direction::Right => (&self.right, &self.left, pos - self.cursor),
+            Direction::Left => (&self.left, &self.right, self.cursor - 1 - pos),
+        };
+
+        // The runs from `pos` in `direction`, the first one starting at `pos`.
+        let mut runs = vec![];
+        if offset >= 0 {
+            let mut skip = offset as u64;
+            for &(s, n) in ahead.iter().rev() {
+                if skip < n {
+                    runs.push((s, n - skip));
+                    skip = 0;
+                } else {
+                    skip -= n;
+                }
+            }
+        } else {
+            // `pos` is behind the start of `ahead`, the cells up to it are
+            // the nearest cells of `behind`.
+            let mut left = (-offset) as u64;
+            for &(s, n) in behind.iter().rev() {
+                if left == 0 {
+                    break;
+                }
+                runs.push((s, n.min(left)));
+                left -= n.min(left);
+            }
+            if left > 0 {
+                runs.push((Sym::zero(), left));
+            }
+            runs.reverse();
+            runs.extend(ahead.iter().rev().copied());
+        }
+
+        let mut count = 0;
+        for (s, n) in runs {
+            if s != symbol {
+                return count.min(max);
+            }
+            count += n;
+            if count >= max {
+                return max;
+            }
+        }
+
+        // Past the runs the tape is blank.
+        if symbol == Sym::zero() {
+            max
+        } else {
+            count.min(max)
+        }
     }
 
-    pub fn iter_from(&'_ self, from: i64) -> impl Iterator<Item = Sym> + '_ {
-        (from..self.0.len() as i64).map(move |i| {
-            if i < 0 {
-                Sym::zero()
-            } else {
-                self.0.get(i as usize).cloned().unwrap_or_else(Sym::zero)
+    fn fill(&mut self, pos: i64, direction: Direction, count: u64, symbol: Sym) {
+        if count == 0 {
+            return;
+        }
+        let first = match direction {
+            Direction::Left => pos - count as i64 + 1,
+            Direction::Right => pos,
+        };
+        self.move_to(first);
+
+        let mut left = count;
+        while left > 0 {
+            match self.right.last_mut() {
+                Some((_, n)) if *n > left => {
+                    *n -= left;
+                    left = 0;
+                }
+                Some((_, n)) => {
+                    left -= *n;
+                    self.right.pop();
+                }
+                None => left = 0,
             }
-        })
+        }
+        push_run(&mut self.right, symbol, count);
+
+        self.leftmost = self.leftmost.min(first);
+        self.rightmost = self.rightmost.max(first + count as i64);
     }
 
-    pub fn iter(&'_ self) -> impl Iterator<Item = Sym> + '_ {
-        self.0.iter().cloned()
+    fn marks(&self) -> usize {
+        let (_, runs) = self.runs();
+        runs.filter(|(s, _)| *s != Sym::zero())
+            .map(|(_, n)| *n as usize)
+            .sum()
     }
+}
 
-    pub fn size(&self) -> usize {
-        self.0.len()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SymbolT;
+
+    fn test_read_write<T: Tape<SymbolT<'2'>>>() {
+        let mut tape = T::default();
+
+        tape.write(2, SymbolT('1'));
+        tape.write(-3, SymbolT('2'));
+
+        assert_eq!(tape.read(2), SymbolT('1'));
+        assert_eq!(tape.read(-3), SymbolT('2'));
+        assert_eq!(tape.read(-1), SymbolT('0'));
+        assert_eq!(tape.read(10), SymbolT('0'));
+        assert_eq!((tape.leftmost(), tape.rightmost()), (-3, 3));
+        assert_eq!(tape.size(), 6);
+        assert_eq!(tape.marks(), 2);
+    }
+
+    fn test_iter<T: Tape<SymbolT<'2'>>>() {
+        let mut tape = T::default();
+
+        tape.write(1, SymbolT('1'));
+        tape.write(-2, SymbolT('2'));
+
+        assert_eq!(
+            tape.iter().map(|s| s.0).collect::<String>(),
+            "2001".to_string()
+        );
+        assert_eq!(
+            tape.iter_between(-4, 3).map(|s| s.0).collect::<String>(),
+            "0020010".to_string()
+        );
+        assert_eq!(
+            tape.iter_between(0, 2).map(|s| s.0).collect::<String>(),
+            "01".to_string()
+        );
+    }
+
+    fn test_run_length<T: Tape<SymbolT<'2'>>>() {
+        let mut tape = T::default();
+
+        tape.fill(-2, Direction::Right, 5, SymbolT('1'));
+        tape.write(0, SymbolT('2'));
+
+        assert_eq!(
+            tape.iter().map(|s| s.0).collect::<String>(),
+            "11211".to_string()
+        );
+        assert_eq!(tape.run_length(-2, Direction::Right, 100), 2);
+        assert_eq!(tape.run_length(2, Direction::Left, 100), 2);
+        assert_eq!(tape.run_length(1, Direction::Right, 100), 2);
+        assert_eq!(tape.run_length(-1, Direction::Left, 1), 1);
+        assert_eq!(tape.run_length(3, Direction::Right, 100), 100);
+        assert_eq!(tape.run_length(-3, Direction::Left, 100), 100);
+        assert_eq!(tape.run_length(-3, Direction::Right, 100), 1);
+
+        tape.fill(1, Direction::Left, 4, SymbolT('0'));
+
+        assert_eq!(
+            tape.iter().map(|s| s.0).collect::<String>(),
+            "00001".to_string()
+        );
+        assert_eq!(tape.marks(), 1);
+        assert_eq!(tape.run_length(-1, Direction::Right, 100), 3);
+        assert_eq!(tape.run_length(-1, Direction::Left, 100), 100);
+    }
+
+    #[test]
+    fn test_vec_tape_read_write() {
+        test_read_write::<VecTape<_>>();
+    }
+
+    #[test]
+    fn test_vec_tape_iter() {
+        test_iter::<VecTape<_>>();
+    }
+
+    #[test]
+    fn test_rle_tape_read_write() {
+        test_read_write::<RleTape<_>>();
+    }
+
+    #[test]
+    fn test_rle_tape_iter() {
+        test_iter::<RleTape<_>>();
+    }
+
+    #[test]
+    fn test_vec_tape_run_length() {
+        test_run_length::<VecTape<_>>();
+    }
+
+    #[test]
+    fn test_rle_tape_run_length() {
+        test_run_length::<RleTape<_>>();
+    }
+
+    #[test]
+    fn test_rle_tape_runs() {
+        let mut tape = RleTape::<SymbolT<'2'>>::default();
+
+        for pos in 0..1000 {
+            tape.write(pos, SymbolT('1'));
+        }
+        for pos in (-1000..0).rev() {
+            tape.write(pos, SymbolT('2'));
+        }
+        tape.write(500, SymbolT('0'));
+
+        assert_eq!(tape.runs_len(), 4);
+        assert_eq!(tape.marks(), 1999);
+        assert_eq!(tape.read(499), SymbolT('1'));
+        assert_eq!(tape.read(500), SymbolT('0'));
+        assert_eq!(tape.read(-1000), SymbolT('2'));
+        assert_eq!(tape.read(-1001), SymbolT('0'));
+
+        let vec = tape.iter().collect::<Vec<_>>();
+        let mut other = VecTape::default();
+        for (pos, s) in (-1000..).zip(vec.iter()) {
+            other.write(pos, *s);
+        }
+        assert_eq!(
+            tape.iter_between(-1010, 1010).collect::<Vec<_>>(),
+            other.iter_between(-1010, 1010).collect::<Vec<_>>()
+        );
+        for pos in (-1010..1010).step_by(7) {
+            for &direction in &[Direction::Left, Direction::Right] {
+                assert_eq!(
+                    tape.run_length(pos, direction, 5000),
+                    other.run_length(pos, direction, 5000)
+                );
+            }
+        }
     }
 }
@@ -1,7 +1,10 @@
 use crate::program::ProgramParseError;
-use std::{convert::TryFrom, fmt::Debug};
+use std::{
+    convert::TryFrom,
+    fmt::{Debug, Display},
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Direction {
     Left,
     Right,
@@ -19,7 +22,16 @@ impl TryFrom<char> for Direction {
     }
 }
 
-pub trait State: Ord + Eq + TryFrom<char> + Copy + Debug {
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Left => write!(f, "L"),
+            Direction::Right => write!(f, "R"),
+        }
+    }
+}
+
+pub trait State: Ord + Eq + TryFrom<char> + Copy + Debug + Display {
     fn states() -> Vec<Self>;
 
     fn initial_state() -> Self;
@@ -27,7 +39,7 @@ pub trait State: Ord + Eq + TryFrom<char> + Copy + Debug {
     fn halt() -> Self;
 }
 
-pub trait Symbol: Ord + Eq + TryFrom<char> + Copy + Debug + ToString {
+pub trait Symbol: Ord + Eq + TryFrom<char> + Copy + Debug + Display {
     fn symbols() -> Vec<Self>;
 
     fn zero() -> Self;
@@ -39,11 +51,11 @@ pub enum StateT<const S: char> {
     Halt,
 }
 
-impl<const S: char> ToString for StateT<S> {
-    fn to_string(&self) -> String {
+impl<const S: char> Display for StateT<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Val(t) => t.to_string(),
-            Self::Halt => "H".to_string(),
+            Self::Val(t) => write!(f, "{}", t),
+            Self::Halt => write!(f, "H"),
         }
     }
 }
@@ -66,13 +78,13 @@ impl<const S: char> TryFrom<char> for StateT<S> {
     type Error = ProgramParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
-        if value == 'H' {
+        if value == 'H' || value == 'Z' {
             Ok(StateT::Halt)
         } else if value >= 'A' && value <= S {
             Ok(StateT::Val(value))
         } else {
             Err(ProgramParseError(format!(
-                "Expected State instruction 'A' to {} or 'H' got {}",
+                "Expected State instruction 'A' to {} or 'H'/'Z' got {}",
                 S, value
             )))
         }
@@ -92,9 +104,9 @@ impl<const S: char> Symbol for SymbolT<S> {
     }
 }
 
-impl<const S: char> ToString for SymbolT<S> {
-    fn to_string(&self) -> String {
-        self.0.to_string()
+impl<const S: char> Display for SymbolT<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
@@ -112,3 +124,96 @@ impl<const S: char> TryFrom<char> for SymbolT<S> {
         }
     }
 }
+
+/// A state of a program whose number of states is only known at runtime,
+/// stored as its index from `A`. Up to 25 states, `A` to `Y`, are
+/// supported, `Z` is the halt state. `H` is an ordinary state here, so the
+/// halt state is always displayed as `Z`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum DynState {
+    Val(u8),
+    Halt,
+}
+
+impl DynState {
+    pub const MAX: usize = 25;
+}
+
+impl Display for DynState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Val(i) => write!(f, "{}", (b'A' + i) as char),
+            Self::Halt => write!(f, "Z"),
+        }
+    }
+}
+
+impl State for DynState {
+    fn states() -> Vec<Self> {
+        (0..Self::MAX as u8).map(DynState::Val).collect()
+    }
+
+    fn initial_state() -> Self {
+        DynState::Val(0)
+    }
+
+    fn halt() -> Self {
+        DynState::Halt
+    }
+}
+
+impl TryFrom<char> for DynState {
+    type Error = ProgramParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'Z' => Ok(DynState::Halt),
+            'A'..='Y' => Ok(DynState::Val(value as u8 - b'A')),
+            _ => Err(ProgramParseError(format!(
+                "Expected State instruction 'A' to 'Y' or 'Z' got {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// A symbol of a program whose number of colors is only known at runtime,
+/// stored as its digit. Up to 10 colors, `0` to `9`, are supported.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct DynSymbol(pub u8);
+
+impl DynSymbol {
+    pub const MAX: usize = 10;
+}
+
+impl Symbol for DynSymbol {
+    fn symbols() -> Vec<Self> {
+        (0..Self::MAX as u8).map(DynSymbol).collect()
+    }
+
+    fn zero() -> Self {
+        DynSymbol(0)
+    }
+}
+
+impl Display for DynSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<char> for DynSymbol {
+    type Error = ProgramParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        value
+            .to_digit(10)
+            .map(|d| DynSymbol(d as u8))
+            .ok_or_else(|| {
+                ProgramParseError(format!(
+                    "Expected Symbol Instruction '0' to '9' found {}",
+                    value
+                ))
+            })
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::turing;
+
+const PROGRAMS: &str = "1RB 1LB  1LA 1RH
+
+1RB 2LB 1RH  2LA 2RB 1LB
+1RB 1LB  1LA 0RB
+1RB
+1RB1LC_1RC1RB_1RD0LE_1LA1LD_1RZ0LA
+";
+
+fn batch(args: &[&str]) -> String {
+    let output = turing(&[&["batch"], args].concat(), PROGRAMS);
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_batch() {
+    let results = batch(&["--limit", "1000", "-c", "100"]);
+    let lines = results.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "1RB 1LB  1LA 1RH: marks 4 steps 6 reason Halt");
+    assert_eq!(
+        lines[1],
+        "1RB 2LB 1RH  2LA 2RB 1LB: marks 9 steps 38 reason Halt"
+    );
+    assert_eq!(
+        lines[2],
+        "1RB 1LB  1LA 0RB: marks 33 steps 100 reason Recurr(3)"
+    );
+    assert!(lines[3].starts_with("1RB: error "));
+    assert!(lines[4].starts_with("1RB1LC_1RC1RB_1RD0LE_1LA1LD_1RZ0LA: "));
+    assert!(lines[4].ends_with("reason XLimit"));
+}
+
+#[test]
+fn test_batch_parallel() {
+    assert_eq!(batch(&["-p", "-a"]), batch(&["-a"]));
+    assert_eq!(batch(&["-p"]), batch(&[]));
+}
+
+#[test]
+fn test_batch_formats() {
+    let csv = batch(&["--format", "csv", "--limit", "1000", "-c", "100"]);
+    let rows = csv.lines().collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 6);
+    assert_eq!(
+        rows[0],
+        "program,marks,steps,reason,period,start,offset,words,undefined,decider,leftmost,rightmost,cells,\
+         head,head_leftmost,head_rightmost,visits,runtime,error"
+    );
+    assert!(rows[3].starts_with("1RB 1LB  1LA 0RB,33,100,Recurr,3,100,,,,recurrence,"));
+    assert!(rows[4].starts_with("1RB,,,Error,"));
+
+    let json = batch(&["--format", "json", "--limit", "1000", "-c", "100"]);
+    let objects = json
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(objects.len(), 5);
+    assert_eq!(objects[0]["program"], "1RB 1LB  1LA 1RH");
+    assert_eq!(objects[0]["marks"], 4);
+    assert_eq!(objects[0]["leftmost"], -2);
+    assert_eq!(objects[2]["reason"], "Recurr");
+    assert_eq!(objects[2]["period"], 3);
+    assert_eq!(objects[2]["decider"], "recurrence");
+    assert_eq!(objects[3]["reason"], "Error");
+    assert!(objects[4]["runtime"].is_f64());
+}
+
+#[test]
+fn test_batch_deciders() {
+    let json = batch(&[
+        "--format",
+        "json",
+        "--deciders",
+        "cycler:100,translated-cycler:1000,simulate:2000",
+    ]);
+    let objects = json
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(objects[0]["reason"], "Halt");
+    assert_eq!(objects[0]["decider"], "cycler");
+    assert_eq!(objects[2]["reason"], "TranslatedCycler");
+    assert_eq!(objects[2]["decider"], "translated-cycler");
+    assert_eq!(objects[4]["reason"], "XLimit");
+    assert_eq!(objects[4]["steps"], 2000);
+    assert!(objects[4]["decider"].is_null());
+}
+
+#[test]
+fn test_batch_deciders_static() {
+    let output = turing(
+        &[
+            "batch",
+            "--format",
+            "json",
+            "--deciders",
+            "simulate:100,backward,closed-language",
+        ],
+        "0RB 1RH  0LA 0RB\n1RB 1RH  0RA 0RA\n",
+    );
+    assert!(output.status.success());
+
+    let objects = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(objects[0]["reason"], "Backward");
+    assert_eq!(objects[0]["decider"], "backward");
+    assert_eq!(objects[1]["reason"], "ClosedLanguage");
+    assert_eq!(objects[1]["decider"], "closed-language");
+}
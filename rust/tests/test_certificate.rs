@@ -0,0 +1,89 @@
+mod common;
+
+use common::turing;
+
+#[test]
+fn test_certificate_verify() {
+    let path = std::env::temp_dir().join(format!("certificate-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let run = turing(&["1RB 0LB  1LA 0RB", "-c", "0", "--certificate", path], "");
+    assert!(run.status.success());
+
+    let verified = turing(&["verify", "1RB 0LB  1LA 0RB", path], "");
+    assert!(verified.status.success());
+    assert_eq!(String::from_utf8(verified.stdout).unwrap(), "Verified\n");
+
+    let other = turing(&["verify", "1RB 0LB  1LA 1RB", path], "");
+    assert!(!other.status.success());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_certificate_verify_backward() {
+    let backward = turing(&["backward", "0RB 1RH  0LA 0RB"], "");
+    assert!(backward.status.success());
+
+    let proof = String::from_utf8(backward.stdout).unwrap();
+
+    assert!(turing(&["verify", "0RB 1RH  0LA 0RB"], &proof)
+        .status
+        .success());
+    assert!(!turing(&["verify", "0RB 1RH  0LA 1RB"], &proof)
+        .status
+        .success());
+    assert!(!turing(&["backward", "1RB 1LB  1LA 1RH"], "")
+        .status
+        .success());
+}
+
+#[test]
+fn test_certificate_verify_ctl() {
+    let ctl = turing(&["ctl", "1RB 1LB  1LA 0RB"], "");
+    assert!(ctl.status.success());
+
+    let certificate = String::from_utf8(ctl.stdout).unwrap();
+
+    assert!(turing(&["verify", "1RB 1LB  1LA 0RB"], &certificate)
+        .status
+        .success());
+    assert!(!turing(&["verify", "1RB 1LB  1LA 1RH"], &certificate)
+        .status
+        .success());
+    assert!(!turing(&["verify", "1RB 1LB  1LA 0RB"], "{}")
+        .status
+        .success());
+}
+
+#[test]
+fn test_certificate_verify_deciders() {
+    let path =
+        std::env::temp_dir().join(format!("certificate-deciders-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let run = turing(
+        &[
+            "1RB 0LB  1LA 0RB",
+            "-",
+            "--format",
+            "json",
+            "--deciders",
+            "simulate:10,recurrence-parallel",
+            "--certificate",
+            path,
+        ],
+        "",
+    );
+    assert!(run.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&run.stdout).unwrap();
+    assert_eq!(report["reason"], "Recurr");
+    assert_eq!(report["decider"], "recurrence-parallel");
+
+    assert!(turing(&["verify", "1RB 0LB  1LA 0RB", path], "")
+        .status
+        .success());
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,213 @@
+use lin_rado_turing::{
+    checkpoint::{Checkpoint, RunOptions},
+    machine::{HaltReason, Machine},
+    program::{parse_program, ProgramT},
+    tape::{RleTape, Tape},
+    types::{State, Symbol},
+};
+
+#[test]
+fn test_checkpoint_resume_halt() {
+    let program = parse_program("1RB 0LC  1RC 1RD  1LA 0RB  0RE 1RH  1LC 1RA").unwrap();
+
+    if let ProgramT::FiveTwo(prog) = program {
+        let mut machine = Machine::new(prog.clone());
+
+        machine.run_until_halt::<std::io::Stdout>(
+            vec![],
+            50000,
+            &mut None,
+            None,
+            None,
+            false,
+            false,
+        );
+
+        assert_eq!(machine.halt().unwrap().reason, HaltReason::XLimit);
+
+        let json = machine.checkpoint(false).to_json();
+        let checkpoint = Checkpoint::from_json(&json).unwrap();
+
+        assert_eq!(checkpoint.steps, 50001);
+
+        let mut restored = Machine::<_, _, RleTape<_>>::restore(prog, &checkpoint).unwrap();
+
+        assert_eq!(restored.steps(), machine.steps());
+        assert_eq!(restored.head(), machine.head());
+        assert_eq!(restored.state(), machine.state());
+        assert!(restored.tape().iter().eq(machine.tape().iter()));
+
+        restored.run_until_halt::<std::io::Stdout>(
+            vec![],
+            200000,
+            &mut None,
+            None,
+            None,
+            false,
+            true,
+        );
+
+        let halt = restored.halt().unwrap();
+
+        assert_eq!(halt.reason, HaltReason::Halt);
+        assert_eq!(halt.steps, 134467);
+        assert_eq!(restored.marks(), 501);
+    }
+}
+
+#[test]
+fn test_checkpoint_resume_recurrence() {
+    let program = parse_program("1RB 1LB  0RC 0LA  1LC 0LA").unwrap();
+
+    if let ProgramT::ThreeTwo(prog) = program {
+        for &snapshots in &[true, false] {
+            let mut machine = Machine::new(prog.clone());
+
+            machine.run_until_halt::<std::io::Stdout>(
+                vec![],
+                110,
+                &mut None,
+                Some(0),
+                None,
+                false,
+                false,
+            );
+
+            assert_eq!(machine.halt().unwrap().reason, HaltReason::XLimit);
+
+            let checkpoint = machine.checkpoint(snapshots);
+
+            assert_eq!(checkpoint.recurrence.is_some(), snapshots);
+
+            let mut restored = Machine::<_, _>::restore(prog.clone(), &checkpoint).unwrap();
+
+            restored.run_until_halt::<std::io::Stdout>(
+                vec![],
+                1000,
+                &mut None,
+                Some(0),
+                None,
+                false,
+                false,
+            );
+
+            let halt = restored.halt().unwrap();
+
+            if snapshots {
+                // The same as running without stopping.
+                assert_eq!(halt.reason, HaltReason::Recurr(24));
+                assert_eq!(halt.steps, 101);
+                assert_eq!(restored.marks(), 9);
+            } else {
+                // The recurrence is found, but only from after the checkpoint.
+                assert_eq!(halt.reason, HaltReason::Recurr(24));
+                assert!(halt.steps > 110);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_checkpoint_resume_in_stretches() {
+    let program = parse_program("1RB 1LC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA").unwrap();
+
+    if let ProgramT::FiveTwo(prog) = program {
+        let mut whole = Machine::new(prog.clone());
+
+        whole.run_until_halt::<std::io::Stdout>(
+            vec![],
+            3000,
+            &mut None,
+            None,
+            Some(0),
+            false,
+            false,
+        );
+
+        let mut machine = Machine::new(prog.clone());
+
+        for limit in (0..=3000).step_by(250) {
+            machine.run_until_halt::<std::io::Stdout>(
+                vec![],
+                limit,
+                &mut None,
+                None,
+                Some(0),
+                false,
+                false,
+            );
+            let checkpoint = machine.checkpoint(true);
+            machine = Machine::restore(prog.clone(), &checkpoint).unwrap();
+        }
+        machine.run_until_halt::<std::io::Stdout>(
+            vec![],
+            3000,
+            &mut None,
+            None,
+            Some(0),
+            false,
+            false,
+        );
+
+        assert_eq!(machine.halt(), whole.halt());
+        assert_eq!(machine.steps(), whole.steps());
+        assert_eq!(machine.marks(), whole.marks());
+        assert!(machine.tape().iter().eq(whole.tape().iter()));
+    }
+}
+
+#[test]
+fn test_checkpoint_save_load() {
+    let program = parse_program("1RB 2LB 1RH  2LA 2RB 1LB").unwrap();
+
+    if let ProgramT::TwoThree(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        machine.run_until_halt::<std::io::Stdout>(vec![], 20, &mut None, None, None, false, false);
+
+        let options = RunOptions {
+            limit: 20,
+            check_recurrence: None,
+            check_blank: None,
+            parallel: false,
+            accelerate: false,
+        };
+        let checkpoint = machine.checkpoint(true).with_options(options);
+
+        let path = std::env::temp_dir().join(format!("checkpoint-{}.json", std::process::id()));
+        // A file that only shares the stem mustn't be touched by the save.
+        let other = path.with_extension("tmp");
+        std::fs::write(&other, "other").unwrap();
+
+        checkpoint.save(&path).unwrap();
+        let loaded = Checkpoint::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&other).unwrap(), "other");
+        std::fs::remove_file(&other).unwrap();
+
+        assert_eq!(loaded, checkpoint);
+        assert_checkpoint_errors(&loaded, machine);
+    }
+}
+
+fn assert_checkpoint_errors<
+    S: State + Send + Sync,
+    Sym: Symbol + Send + Sync,
+    T: Tape<Sym> + Send + Sync,
+>(
+    checkpoint: &Checkpoint,
+    machine: Machine<S, Sym, T>,
+) {
+    let mut bad = checkpoint.clone();
+    bad.state = "Q".to_string();
+
+    assert!(Machine::<S, Sym, T>::restore(machine.program().clone(), &bad).is_err());
+
+    let mut bad = checkpoint.clone();
+    bad.tape.runs.push(("7".to_string(), 1));
+
+    assert!(Machine::<S, Sym, T>::restore(machine.program().clone(), &bad).is_err());
+
+    assert!(Checkpoint::from_json("{}").is_err());
+}
@@ -0,0 +1,62 @@
+use lin_rado_turing::{
+    enumerate::Enumerate,
+    machine::HaltReason,
+    types::{State, StateT, Symbol, SymbolT},
+};
+use std::collections::BTreeSet;
+
+const BUSY_BEAVERS: &[(&str, usize)] = &[
+    // Step counts include the step that reaches the halting instruction.
+    ("2-2", 6),
+    ("3-2", 21),
+    ("2-3", 38),
+];
+
+fn max_steps<S: State + Send + Sync, Sym: Symbol + Send + Sync>(limit: usize) -> usize {
+    Enumerate::<S, Sym>::new(limit, None)
+        .filter(|(_, halt)| matches!(halt.reason, HaltReason::Undefined(_)))
+        .map(|(_, halt)| halt.steps)
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn test_enumerate_busy_beavers() {
+    for &(complexity, steps) in BUSY_BEAVERS {
+        let found = match complexity {
+            "2-2" => max_steps::<StateT<'B'>, SymbolT<'1'>>(100),
+            "3-2" => max_steps::<StateT<'C'>, SymbolT<'1'>>(100),
+            "2-3" => max_steps::<StateT<'B'>, SymbolT<'2'>>(100),
+            _ => unreachable!(),
+        };
+        assert_eq!(found, steps, "{}", complexity);
+    }
+}
+
+#[test]
+fn test_enumerate_unique() {
+    let programs = Enumerate::<StateT<'C'>, SymbolT<'1'>>::new(100, None)
+        .map(|(prog, _)| prog.to_string())
+        .collect::<Vec<_>>();
+
+    let unique = programs.iter().collect::<BTreeSet<_>>();
+
+    assert_eq!(programs.len(), unique.len());
+}
+
+#[test]
+fn test_enumerate_tree_normal_form() {
+    let programs = Enumerate::<StateT<'B'>, SymbolT<'1'>>::new(100, None)
+        .map(|(prog, _)| prog.to_string())
+        .collect::<BTreeSet<_>>();
+
+    // The 2-state busy beaver, with its halting instruction undefined.
+    assert!(programs.contains("1RB 1LB  1LA ..."));
+    // Its mirror image and its state renaming are left out.
+    assert!(!programs.contains("1LB 1RB  1RA ..."));
+    assert!(programs.iter().all(|p| p.starts_with("0RA")
+        || p.starts_with("1RA")
+        || p.starts_with("0RB")
+        || p.starts_with("1RB")
+        || p.starts_with("...")));
+}
@@ -83,19 +83,35 @@ const BLANK_FAST: &[(&str, usize, &str)] = &[
 fn test_machine_blanks() {
     for (prog_str, steps, complexity) in BLANK_FAST {
         println!("{}", prog_str);
-        parse_program_and_assert(prog_str, *steps, complexity);
+        parse_program_and_assert(prog_str, *steps, complexity, false);
+    }
+}
+
+#[test]
+fn test_machine_blanks_accelerated() {
+    for (prog_str, steps, complexity) in BLANK_FAST {
+        parse_program_and_assert(prog_str, *steps, complexity, true);
     }
 }
 
 fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>(
     prog: Program<S, Sym>,
     steps: usize,
+    accelerate: bool,
 ) {
     let mut machine = Machine::new(prog);
 
     let blank = Some(0);
 
-    machine.run_until_halt::<std::io::Stdout>(vec![], steps + 20, &mut None, None, blank, false);
+    machine.run_until_halt::<std::io::Stdout>(
+        vec![],
+        steps + 20,
+        &mut None,
+        None,
+        blank,
+        false,
+        accelerate,
+    );
 
     let halt = machine.halt();
 
@@ -110,24 +126,25 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
     assert_eq!(machine.marks(), 0);
 }
 
-fn parse_program_and_assert(prog_str: &str, steps: usize, _complexity: &str) {
+fn parse_program_and_assert(prog_str: &str, steps: usize, _complexity: &str, accelerate: bool) {
     let program = parse_program(prog_str).unwrap();
 
     match program {
-        ProgramT::TwoTwo(prog) => assert_machine(prog, steps),
-        ProgramT::TwoThree(prog) => assert_machine(prog, steps),
-        ProgramT::TwoFour(prog) => assert_machine(prog, steps),
-        ProgramT::ThreeTwo(prog) => assert_machine(prog, steps),
-        ProgramT::ThreeThree(prog) => assert_machine(prog, steps),
-        ProgramT::ThreeFour(prog) => assert_machine(prog, steps),
-        ProgramT::FourTwo(prog) => assert_machine(prog, steps),
-        ProgramT::FourThree(prog) => assert_machine(prog, steps),
-        ProgramT::FourFour(prog) => assert_machine(prog, steps),
-        ProgramT::FiveTwo(prog) => assert_machine(prog, steps),
-        ProgramT::FiveThree(prog) => assert_machine(prog, steps),
-        ProgramT::FiveFour(prog) => assert_machine(prog, steps),
-        ProgramT::SixTwo(prog) => assert_machine(prog, steps),
-        ProgramT::SixThree(prog) => assert_machine(prog, steps),
-        ProgramT::SixFour(prog) => assert_machine(prog, steps),
+        ProgramT::TwoTwo(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::TwoThree(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::TwoFour(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::ThreeTwo(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::ThreeThree(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::ThreeFour(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::FourTwo(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::FourThree(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::FourFour(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::FiveTwo(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::FiveThree(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::FiveFour(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::SixTwo(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::SixThree(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::SixFour(prog) => assert_machine(prog, steps, accelerate),
+        ProgramT::Dynamic(prog) => assert_machine(prog, steps, accelerate),
     }
 }
@@ -0,0 +1,73 @@
+use lin_rado_turing::{
+    machine::{HaltReason, Machine},
+    program::{parse_program, ProgramT},
+    types::{StateT, SymbolT},
+};
+
+#[test]
+fn test_machine_configurations() {
+    let program = parse_program("1RB 1LB  1LA 1RH").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        let configs = machine.configurations(100).collect::<Vec<_>>();
+
+        assert_eq!(configs.len(), 7);
+        assert_eq!(
+            configs.iter().map(|c| c.step).collect::<Vec<_>>(),
+            (0..=6).collect::<Vec<_>>()
+        );
+        assert_eq!(configs[0].state, StateT::Val('A'));
+        assert_eq!(configs[0].head, 0);
+        assert_eq!(configs[0].tape, vec![SymbolT('0')]);
+        assert_eq!(configs[3].head, -1);
+        assert_eq!(configs[3].leftmost, -1);
+        assert_eq!(
+            configs[3].to_string(),
+            "       3 Val('B')  [0]11".to_string()
+        );
+        assert_eq!(configs[6].state, StateT::Halt);
+        assert_eq!(
+            configs[6].tape.iter().map(|s| s.0).collect::<String>(),
+            "1111"
+        );
+
+        assert_eq!(machine.halt().unwrap().reason, HaltReason::Halt);
+        assert!(machine.configurations(100).nth(1).is_none());
+    }
+}
+
+#[test]
+fn test_machine_configurations_limit() {
+    let program = parse_program("1RB 0LB  1LA 0RB").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        let heads = machine
+            .configurations(1000)
+            .step_by(100)
+            .map(|c| c.head)
+            .collect::<Vec<_>>();
+
+        assert_eq!(heads.len(), 11);
+        assert_eq!(machine.steps(), 1000);
+        assert!(machine.halt().is_none());
+    }
+}
+
+#[test]
+fn test_machine_configurations_undefined() {
+    let program = parse_program("1RB 1LB  1LA ...").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        assert_eq!(machine.configurations(100).count(), 6);
+        assert_eq!(
+            machine.halt().unwrap().reason,
+            HaltReason::Undefined("B1".to_string())
+        );
+    }
+}
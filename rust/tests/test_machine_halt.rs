@@ -1,6 +1,12 @@
 use lin_rado_turing::{
-    machine::Machine,
-    program::{parse_program, Program, ProgramT},
+    decider::{
+        backward::{backward_reasoning, Backward},
+        bouncer::check_bouncer,
+        closed_language::find_closed_language,
+    },
+    machine::{HaltReason, Machine},
+    program::{parse_dynamic_program, parse_program, Program, ProgramT},
+    tape::{RleTape, Tape},
     types::{State, Symbol},
 };
 
@@ -98,10 +104,98 @@ const HALTS_SLOWLY: &[(&str, usize, usize, &str)] = &[
     ),
 ];
 
+// Complexities without a fixed type.
+const HALTS_DYNAMIC: &[(&str, usize, usize, &str)] = &[
+    // 2/4 runner-up with an unused color
+    ("1RB 3LA 1LA 1RA ...  2LA 1RH 3RA 3RB ...", 90, 7195, "2-5"),
+    // Milton Green (1964) with an unused state
+    (
+        "1RB 1LH  0RC 1RC  0RD 0RC  1RE 1LA  0RF 0RE  1LF 1LD  ... ...",
+        35,
+        436,
+        "7-2",
+    ),
+    // 4/2 BB with its last state renamed to `H`, so halting is `Z`
+    (
+        "1RB1LB_1LA0LC_1RZ1LH_------_------_------_------_1RH0RA",
+        13,
+        107,
+        "8-2",
+    ),
+];
+
 #[test]
 fn test_machine_halts() {
     for &(prog_str, marks, steps, complexity) in HALTS {
-        parse_program_and_assert(prog_str, marks, steps, complexity, false);
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, false, false);
+    }
+}
+
+#[test]
+fn test_machine_halts_rle() {
+    for &(prog_str, marks, steps, complexity) in HALTS {
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, true, false);
+    }
+}
+
+#[test]
+fn test_machine_halts_accelerated() {
+    for &(prog_str, marks, steps, complexity) in HALTS {
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, true, true);
+    }
+}
+
+#[test]
+fn test_machine_halts_dynamic() {
+    for &(prog_str, marks, steps, _complexity) in HALTS.iter().chain(HALTS_DYNAMIC) {
+        let prog = parse_dynamic_program(prog_str).unwrap();
+        assert_machine(prog.clone(), marks, steps, false, false, false);
+        assert_machine(prog, marks, steps, false, true, true);
+    }
+    for &(prog_str, marks, steps, complexity) in HALTS_DYNAMIC {
+        assert!(matches!(
+            parse_program(prog_str).unwrap(),
+            ProgramT::Dynamic(_)
+        ));
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, false, false);
+    }
+}
+
+#[test]
+fn test_machine_halts_backward() {
+    for &(prog_str, ..) in HALTS.iter().chain(HALTS_SLOWLY).chain(HALTS_DYNAMIC) {
+        let prog = parse_dynamic_program(prog_str).unwrap();
+        assert_eq!(backward_reasoning(&prog, 30, None), Backward::Unknown);
+    }
+}
+
+#[test]
+fn test_machine_halts_bouncer() {
+    for &(prog_str, marks, steps, _complexity) in HALTS.iter().chain(HALTS_DYNAMIC) {
+        let mut machine = Machine::new(parse_dynamic_program(prog_str).unwrap());
+        check_bouncer(&mut machine, steps);
+
+        let halt = machine.halt().unwrap();
+
+        assert_eq!(halt.reason, HaltReason::Halt);
+        assert_eq!(halt.steps, steps);
+        assert_eq!(machine.marks(), marks);
+    }
+}
+
+#[test]
+fn test_machine_halts_closed_language() {
+    for &(prog_str, ..) in HALTS.iter().chain(HALTS_SLOWLY).chain(HALTS_DYNAMIC) {
+        let prog = parse_dynamic_program(prog_str).unwrap();
+        assert_eq!(find_closed_language(&prog, 2, None), None);
+    }
+}
+
+#[test]
+fn test_machine_halts_normalized() {
+    for &(prog_str, marks, steps, complexity) in HALTS {
+        let normalized = parse_program(prog_str).unwrap().normalize(true).to_string();
+        parse_program_and_assert(&normalized, marks, steps, complexity, false, false, false);
     }
 }
 
@@ -109,7 +203,23 @@ fn test_machine_halts() {
 #[cfg_attr(not(feature = "slow-tests"), ignore)]
 fn test_machine_halts_slowly() {
     for &(prog_str, marks, steps, complexity) in HALTS_SLOWLY {
-        parse_program_and_assert(prog_str, marks, steps, complexity, false);
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, false, false);
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "slow-tests"), ignore)]
+fn test_machine_halts_slowly_rle() {
+    for &(prog_str, marks, steps, complexity) in HALTS_SLOWLY {
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, true, false);
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "slow-tests"), ignore)]
+fn test_machine_halts_slowly_accelerated() {
+    for &(prog_str, marks, steps, complexity) in HALTS_SLOWLY {
+        parse_program_and_assert(prog_str, marks, steps, complexity, false, true, true);
     }
 }
 
@@ -118,10 +228,42 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
     marks: usize,
     steps: usize,
     parallel: bool,
+    rle: bool,
+    accelerate: bool,
 ) {
-    let mut machine = Machine::new(prog);
+    if rle {
+        assert_machine_with_tape(
+            Machine::with_tape(prog, RleTape::default()),
+            marks,
+            steps,
+            parallel,
+            accelerate,
+        );
+    } else {
+        assert_machine_with_tape(Machine::new(prog), marks, steps, parallel, accelerate);
+    }
+}
 
-    machine.run_until_halt::<std::io::Stdout>(vec![], steps, &mut None, None, None, parallel);
+fn assert_machine_with_tape<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    mut machine: Machine<S, Sym, T>,
+    marks: usize,
+    steps: usize,
+    parallel: bool,
+    accelerate: bool,
+) {
+    machine.run_until_halt::<std::io::Stdout>(
+        vec![],
+        steps,
+        &mut None,
+        None,
+        None,
+        parallel,
+        accelerate,
+    );
 
     let halt = machine.halt();
 
@@ -140,24 +282,27 @@ fn parse_program_and_assert(
     steps: usize,
     _complexity: &str,
     parallel: bool,
+    rle: bool,
+    accelerate: bool,
 ) {
     let program = parse_program(prog_str).unwrap();
 
     match program {
-        ProgramT::TwoTwo(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::TwoThree(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::TwoFour(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::ThreeTwo(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::ThreeThree(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::ThreeFour(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::FourTwo(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::FourThree(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::FourFour(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::FiveTwo(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::FiveThree(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::FiveFour(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::SixTwo(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::SixThree(prog) => assert_machine(prog, marks, steps, parallel),
-        ProgramT::SixFour(prog) => assert_machine(prog, marks, steps, parallel),
+        ProgramT::TwoTwo(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::TwoThree(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::TwoFour(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::ThreeTwo(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::ThreeThree(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::ThreeFour(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::FourTwo(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::FourThree(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::FourFour(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::FiveTwo(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::FiveThree(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::FiveFour(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::SixTwo(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::SixThree(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::SixFour(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
+        ProgramT::Dynamic(prog) => assert_machine(prog, marks, steps, parallel, rle, accelerate),
     }
 }
@@ -1,4 +1,5 @@
 use lin_rado_turing::{
+    certificate::Certificate,
     machine::{HaltReason, Machine},
     program::{parse_program, Program, ProgramT},
     types::{State, Symbol},
@@ -86,6 +87,7 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
         check,
         None,
         parallel,
+        false,
     );
 
     let halt = machine.halt();
@@ -98,6 +100,10 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
         (machine.marks(), halt.steps, &halt.reason),
         (marks, steps, &HaltReason::Quasihalt(period))
     );
+
+    let certificate = Certificate::new(machine.program(), halt).unwrap();
+
+    assert_eq!(certificate.verify(machine.program()), Ok(()));
 }
 
 fn parse_program_and_assert(
@@ -126,5 +132,6 @@ fn parse_program_and_assert(
         ProgramT::SixTwo(prog) => assert_machine(prog, marks, steps, period, parallel),
         ProgramT::SixThree(prog) => assert_machine(prog, marks, steps, period, parallel),
         ProgramT::SixFour(prog) => assert_machine(prog, marks, steps, period, parallel),
+        ProgramT::Dynamic(prog) => assert_machine(prog, marks, steps, period, parallel),
     }
 }
@@ -1,6 +1,9 @@
 use lin_rado_turing::{
+    certificate::Certificate,
+    decider::{cycler::check_cycler, translated_cycler::check_translated_cycler},
     machine::{HaltReason, Machine},
     program::{parse_program, Program, ProgramT},
+    tape::{RleTape, Tape},
     types::{State, Symbol},
 };
 
@@ -92,7 +95,15 @@ const RECURRENCE: &[(&str, usize, usize, usize, &str)] = &[
 fn test_machine_recurrence() {
     for (prog_str, marks, steps, period, complexity) in RECURRENCE {
         println!("{}", prog_str);
-        parse_program_and_assert(prog_str, *marks, *steps, *period, complexity, false);
+        parse_program_and_assert(prog_str, *marks, *steps, *period, complexity, false, false);
+    }
+}
+
+#[test]
+fn test_machine_recurrence_rle() {
+    for (prog_str, marks, steps, period, complexity) in RECURRENCE {
+        println!("{}", prog_str);
+        parse_program_and_assert(prog_str, *marks, *steps, *period, complexity, false, true);
     }
 }
 
@@ -100,7 +111,23 @@ fn test_machine_recurrence() {
 fn test_machine_recurrence_parallel() {
     for (prog_str, marks, steps, period, complexity) in RECURRENCE {
         println!("{}", prog_str);
-        parse_program_and_assert(prog_str, *marks, *steps, *period, complexity, true);
+        parse_program_and_assert(prog_str, *marks, *steps, *period, complexity, true, false);
+    }
+}
+
+#[test]
+fn test_translated_cycler() {
+    for (prog_str, marks, steps, period, _complexity) in RECURRENCE {
+        println!("{}", prog_str);
+        parse_program_and_check_translated(prog_str, *marks, *steps, *period);
+    }
+}
+
+#[test]
+fn test_cycler() {
+    for (prog_str, marks, steps, period, _complexity) in RECURRENCE {
+        println!("{}", prog_str);
+        parse_program_and_check_cycler(prog_str, *marks, *steps, *period);
     }
 }
 
@@ -110,9 +137,32 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
     steps: usize,
     period: usize,
     parallel: bool,
+    rle: bool,
 ) {
-    let mut machine = Machine::new(prog);
+    if rle {
+        assert_machine_with_tape(
+            Machine::with_tape(prog, RleTape::default()),
+            marks,
+            steps,
+            period,
+            parallel,
+        );
+    } else {
+        assert_machine_with_tape(Machine::new(prog), marks, steps, period, parallel);
+    }
+}
 
+fn assert_machine_with_tape<
+    S: State + Send + Sync + ToString,
+    Sym: Symbol + Send + Sync + ToString,
+    T: Tape<Sym> + Send + Sync,
+>(
+    mut machine: Machine<S, Sym, T>,
+    marks: usize,
+    steps: usize,
+    period: usize,
+    parallel: bool,
+) {
     let check = if steps < 256 { Some(0) } else { Some(steps) };
 
     machine.run_until_halt::<std::io::Stdout>(
@@ -122,6 +172,7 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
         check,
         None,
         parallel,
+        false,
     );
 
     let halt = machine.halt();
@@ -135,6 +186,10 @@ fn assert_machine<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync +
     assert_eq!(halt.steps, steps);
 
     assert_eq!(machine.marks(), marks);
+
+    let certificate = Certificate::new(machine.program(), halt).unwrap();
+
+    assert_eq!(certificate.verify(machine.program()), Ok(()));
 }
 
 fn parse_program_and_assert(
@@ -144,24 +199,134 @@ fn parse_program_and_assert(
     period: usize,
     _complexity: &str,
     parallel: bool,
+    rle: bool,
 ) {
     let program = parse_program(prog_str).unwrap();
 
     match program {
-        ProgramT::TwoTwo(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::TwoThree(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::TwoFour(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::ThreeTwo(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::ThreeThree(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::ThreeFour(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::FourTwo(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::FourThree(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::FourFour(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::FiveTwo(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::FiveThree(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::FiveFour(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::SixTwo(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::SixThree(prog) => assert_machine(prog, marks, steps, period, parallel),
-        ProgramT::SixFour(prog) => assert_machine(prog, marks, steps, period, parallel),
+        ProgramT::TwoTwo(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::TwoThree(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::TwoFour(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::ThreeTwo(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::ThreeThree(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::ThreeFour(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::FourTwo(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::FourThree(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::FourFour(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::FiveTwo(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::FiveThree(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::FiveFour(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::SixTwo(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::SixThree(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::SixFour(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+        ProgramT::Dynamic(prog) => assert_machine(prog, marks, steps, period, parallel, rle),
+    }
+}
+
+/// Translated cyclers are found with the same period, though maybe starting
+/// later. The others must be recurrences that stay where they are.
+fn check_translated<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>(
+    prog: Program<S, Sym>,
+    steps: usize,
+    period: usize,
+) {
+    let mut machine = Machine::new(prog.clone());
+
+    check_translated_cycler(&mut machine, steps + 3 * period);
+
+    let halt = machine.halt().unwrap();
+
+    match halt.reason {
+        HaltReason::TranslatedCycler(p, offset) => {
+            assert_eq!(p, period);
+            assert_ne!(offset, 0);
+            assert!(halt.steps >= steps);
+        }
+        HaltReason::XLimit => {
+            let mut machine = Machine::new(prog);
+            let mut heads = machine.configurations(steps + period).map(|c| c.head);
+
+            assert_eq!(heads.nth(steps), heads.nth(period - 1));
+        }
+        ref reason => panic!("Unexpected {:?}", reason),
+    }
+}
+
+fn parse_program_and_check_translated(prog_str: &str, _marks: usize, steps: usize, period: usize) {
+    let program = parse_program(prog_str).unwrap();
+
+    match program {
+        ProgramT::TwoTwo(prog) => check_translated(prog, steps, period),
+        ProgramT::TwoThree(prog) => check_translated(prog, steps, period),
+        ProgramT::TwoFour(prog) => check_translated(prog, steps, period),
+        ProgramT::ThreeTwo(prog) => check_translated(prog, steps, period),
+        ProgramT::ThreeThree(prog) => check_translated(prog, steps, period),
+        ProgramT::ThreeFour(prog) => check_translated(prog, steps, period),
+        ProgramT::FourTwo(prog) => check_translated(prog, steps, period),
+        ProgramT::FourThree(prog) => check_translated(prog, steps, period),
+        ProgramT::FourFour(prog) => check_translated(prog, steps, period),
+        ProgramT::FiveTwo(prog) => check_translated(prog, steps, period),
+        ProgramT::FiveThree(prog) => check_translated(prog, steps, period),
+        ProgramT::FiveFour(prog) => check_translated(prog, steps, period),
+        ProgramT::SixTwo(prog) => check_translated(prog, steps, period),
+        ProgramT::SixThree(prog) => check_translated(prog, steps, period),
+        ProgramT::SixFour(prog) => check_translated(prog, steps, period),
+        ProgramT::Dynamic(prog) => check_translated(prog, steps, period),
+    }
+}
+
+/// Recurrences that stay where they are are cyclers, found from the same
+/// step with the same period. The others must move along the tape.
+fn check_cycler_same<S: State + Send + Sync + ToString, Sym: Symbol + Send + Sync + ToString>(
+    prog: Program<S, Sym>,
+    marks: usize,
+    steps: usize,
+    period: usize,
+) {
+    let mut machine = Machine::new(prog.clone());
+
+    check_cycler(&mut machine, 4 * (steps + period));
+
+    let halt = machine.halt().unwrap();
+
+    match halt.reason {
+        HaltReason::Cycler(p) => {
+            assert_eq!(p, period);
+            assert_eq!(halt.steps, steps);
+
+            let mut machine = Machine::new(prog);
+            machine.configurations(steps).last();
+            assert_eq!(machine.marks(), marks);
+        }
+        HaltReason::XLimit => {
+            let mut machine = Machine::new(prog);
+            let mut heads = machine.configurations(steps + period).map(|c| c.head);
+
+            assert_ne!(heads.nth(steps), heads.nth(period - 1));
+        }
+        ref reason => panic!("Unexpected {:?}", reason),
+    }
+}
+
+fn parse_program_and_check_cycler(prog_str: &str, marks: usize, steps: usize, period: usize) {
+    let program = parse_program(prog_str).unwrap();
+
+    match program {
+        ProgramT::TwoTwo(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::TwoThree(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::TwoFour(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::ThreeTwo(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::ThreeThree(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::ThreeFour(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::FourTwo(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::FourThree(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::FourFour(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::FiveTwo(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::FiveThree(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::FiveFour(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::SixTwo(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::SixThree(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::SixFour(prog) => check_cycler_same(prog, marks, steps, period),
+        ProgramT::Dynamic(prog) => check_cycler_same(prog, marks, steps, period),
     }
 }
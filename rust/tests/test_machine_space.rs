@@ -0,0 +1,75 @@
+use lin_rado_turing::{
+    checkpoint::Checkpoint,
+    machine::Machine,
+    program::{parse_program, ProgramT},
+    tape::RleTape,
+    types::StateT,
+};
+
+#[test]
+fn test_machine_space() {
+    let program = parse_program("1RB 1LB  1LA 1RH").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        assert_eq!(machine.space().cells(), 1);
+
+        machine.run_until_halt::<std::io::Stdout>(vec![], 100, &mut None, None, None, false, false);
+
+        let space = machine.space();
+
+        assert_eq!(space.cells(), 4);
+        assert_eq!((space.leftmost, space.rightmost), (-2, 1));
+        assert_eq!(machine.head(), 0);
+        assert_eq!(
+            space.visits.iter().collect::<Vec<_>>(),
+            vec![(&StateT::Val('A'), &3), (&StateT::Val('B'), &3)]
+        );
+    }
+}
+
+#[test]
+fn test_machine_space_accelerated() {
+    let program = parse_program("1RB 1LC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA").unwrap();
+
+    if let ProgramT::FiveTwo(prog) = program {
+        let mut machine = Machine::new(prog.clone());
+        let mut accelerated = Machine::with_tape(prog.clone(), RleTape::default());
+
+        machine.run_until_halt::<std::io::Stdout>(
+            vec![],
+            20000,
+            &mut None,
+            None,
+            None,
+            false,
+            false,
+        );
+        accelerated.run_until_halt::<std::io::Stdout>(
+            vec![],
+            20000,
+            &mut None,
+            None,
+            None,
+            false,
+            true,
+        );
+
+        assert_eq!(machine.space(), accelerated.space());
+        assert_eq!(
+            machine.space().visits.values().sum::<usize>(),
+            machine.steps()
+        );
+
+        let checkpoint = machine.checkpoint(false);
+        let restored = Machine::<_, _>::restore(prog.clone(), &checkpoint).unwrap();
+
+        assert_eq!(restored.space(), machine.space());
+
+        // The statistics are part of every checkpoint.
+        let mut json = serde_json::to_value(&checkpoint).unwrap();
+        json.as_object_mut().unwrap().remove("space");
+        assert!(Checkpoint::from_json(&json.to_string()).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use lin_rado_turing::{
+    machine::{Halt, HaltReason, Machine, Step},
+    program::{parse_program, ProgramT},
+    tape::Tape,
+    types::{Direction, StateT, SymbolT},
+};
+
+#[test]
+fn test_machine_step() {
+    let program = parse_program("1RB 1LB  1LA 1RH").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        assert_eq!(machine.head(), 0);
+        assert_eq!(machine.state(), StateT::Val('A'));
+        assert_eq!(machine.steps(), 0);
+
+        let step = machine.step().unwrap();
+
+        assert_eq!(
+            step,
+            Step {
+                read: SymbolT('0'),
+                instruction: Some((StateT::Val('B'), SymbolT('1'), Direction::Right)),
+                halt: None,
+            }
+        );
+        assert_eq!(machine.head(), 1);
+        assert_eq!(machine.state(), StateT::Val('B'));
+        assert_eq!(machine.tape().read(0), SymbolT('1'));
+        assert_eq!(machine.steps(), 1);
+
+        let mut last = step;
+        while let Some(step) = machine.step() {
+            last = step;
+        }
+
+        assert_eq!(last.read, SymbolT('1'));
+        assert_eq!(last.halt, Some(Halt::new(6, HaltReason::Halt)));
+        assert_eq!(machine.state(), StateT::Halt);
+        assert_eq!(machine.steps(), 6);
+        assert_eq!(machine.marks(), 4);
+        assert_eq!(
+            machine.tape().iter().map(|s| s.0).collect::<String>(),
+            "1111"
+        );
+        assert!(machine.step().is_none());
+    }
+}
+
+#[test]
+fn test_machine_step_undefined() {
+    let program = parse_program("1RB 1LB  1LA ...").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = Machine::new(prog);
+
+        for _ in 0..5 {
+            assert_eq!(machine.step().unwrap().halt, None);
+        }
+
+        let step = machine.step().unwrap();
+
+        assert_eq!(step.read, SymbolT('1'));
+        assert_eq!(step.instruction, None);
+        assert_eq!(
+            step.halt,
+            Some(Halt::new(6, HaltReason::Undefined("B1".to_string())))
+        );
+        assert_eq!(machine.head(), -1);
+        assert_eq!(machine.state(), StateT::Val('B'));
+        assert!(machine.step().is_none());
+    }
+}
+
+#[test]
+fn test_machine_step_matches_run() {
+    let program = parse_program("1RB 1LC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA").unwrap();
+
+    if let ProgramT::FiveTwo(prog) = program {
+        let mut stepped = Machine::new(prog.clone());
+        let mut run = Machine::new(prog);
+
+        for _ in 0..1000 {
+            stepped.step();
+        }
+        run.run_until_halt::<std::io::Stdout>(vec![], 999, &mut None, None, None, false, false);
+
+        assert_eq!(stepped.steps(), 1000);
+        assert_eq!(stepped.head(), run.head());
+        assert_eq!(stepped.state(), run.state());
+        assert_eq!(
+            stepped.tape().iter().collect::<Vec<_>>(),
+            run.tape().iter().collect::<Vec<_>>()
+        );
+    }
+}
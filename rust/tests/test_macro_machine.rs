@@ -0,0 +1,152 @@
+use lin_rado_turing::{
+    machine::HaltReason,
+    macro_machine::MacroMachine,
+    program::{parse_program, Program, ProgramT},
+    types::{State, Symbol},
+};
+
+const HALTS: &[(&str, usize, usize, &str)] = &[
+    // 2/2 BB
+    ("1RB 1LB  1LA 1RH", 4, 6, "2-2"),
+    // 3/2 BB
+    ("1RB 1RH  1LB 0RC  1LC 1LA", 5, 21, "3-2"), // shift
+    ("1RB 1LC  1RC 1RH  1LA 0LB", 6, 11, "3-2"), // sigma
+    // 2/3 BB
+    ("1RB 2LB 1RH  2LA 2RB 1LB", 9, 38, "2-3"),
+    // 4/2 BB
+    ("1RB 1LB  1LA 0LC  1RH 1LD  1RD 0RA", 13, 107, "4-2"), // shift
+    ("1RB 0RC  1LA 1RA  1RH 1RD  1LD 0LB", 13, 96, "4-2"),  // sigma
+    // 2/4 Runners-up
+    ("1RB 3LA 1LA 1RA  2LA 1RH 3RA 3RB", 90, 7195, "2-4"),
+    ("1RB 2RB 3LA 2RA  1LA 3RB 1RH 1LB", 60, 2351, "2-4"),
+    // Castor diligentissimus et primus et perpetuus (Castor schultis)
+    (
+        "1RB 0LC  1RC 1RD  1LA 0RB  0RE 1RH  1LC 1RA",
+        501,
+        134467,
+        "5-2",
+    ),
+    // Castor scientificus
+    ("0RB 0LA  0RC 0RH  1RD 1LE  1LA 0LD  1RC 1RE", 0, 187, "5-2"),
+];
+
+const HALTS_SLOWLY: &[(&str, usize, usize, &str)] = &[
+    // 3/3 Surprise-in-a-box
+    ("1RB 2LB 1LC  1LA 2RB 1RB  1RH 2LA 0LC", 31, 2315619, "3-3"),
+    // 2/4 BB
+    ("1RB 2LA 1RA 1RA  1LB 1LA 3RB 1RH", 2050, 3932964, "2-4"),
+    // 5/2 BB
+    (
+        "1RB 1LC  1RC 1RB  1RD 0LE  1LA 1LD  1RH 0LA",
+        4098,
+        47176870,
+        "5-2",
+    ),
+];
+
+#[test]
+fn test_macro_machine_halts() {
+    for &(prog_str, marks, steps, complexity) in HALTS {
+        for block_size in 1..=4 {
+            parse_program_and_assert(prog_str, marks, steps, complexity, block_size);
+        }
+    }
+}
+
+#[test]
+fn test_macro_machine_undefined() {
+    let program = parse_program("1RB 1LB  1LA ...").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        let mut machine = MacroMachine::new(prog, 3);
+
+        machine.run_until_halt::<std::io::Stdout>(100, &mut None);
+
+        let halt = machine.halt().unwrap();
+
+        assert_eq!(halt.reason, HaltReason::Undefined("B1".to_string()));
+        assert_eq!(halt.steps, 6);
+        assert_eq!(machine.marks(), 4);
+    }
+}
+
+#[test]
+fn test_macro_machine_cycler() {
+    // Goes back and forth over the same two cells, which fit in one block.
+    let program = parse_program("1RB 1RB  1LA 1LA").unwrap();
+
+    if let ProgramT::TwoTwo(prog) = program {
+        for block_size in 2..=4 {
+            let mut machine = MacroMachine::new(prog.clone(), block_size);
+
+            machine.run_until_halt::<std::io::Stdout>(100, &mut None);
+
+            let halt = machine.halt().unwrap();
+
+            assert_eq!(halt.reason, HaltReason::Cycler(2));
+            assert_eq!(halt.steps, 2);
+            assert_eq!(machine.marks(), 2);
+        }
+    }
+}
+
+#[test]
+fn test_macro_machine_halts_slowly() {
+    // Runs of blocks are crossed in one go, so these are quick as macro
+    // machines.
+    for &(prog_str, marks, steps, complexity) in HALTS_SLOWLY {
+        parse_program_and_assert(prog_str, marks, steps, complexity, 3);
+    }
+}
+
+fn assert_machine<S: State + ToString, Sym: Symbol + ToString>(
+    prog: Program<S, Sym>,
+    marks: usize,
+    steps: usize,
+    block_size: usize,
+) {
+    let mut machine = MacroMachine::new(prog, block_size);
+
+    machine.run_until_halt::<std::io::Stdout>(steps, &mut None);
+
+    let halt = machine.halt();
+
+    assert!(halt.is_some());
+
+    let halt = halt.unwrap();
+
+    assert_eq!(halt.reason, HaltReason::Halt);
+
+    assert_eq!(halt.steps, steps);
+
+    assert_eq!(machine.marks(), marks);
+}
+
+fn parse_program_and_assert(
+    prog_str: &str,
+    marks: usize,
+    steps: usize,
+    _complexity: &str,
+    block_size: usize,
+) {
+    let program = parse_program(prog_str).unwrap();
+
+    match program {
+        ProgramT::TwoTwo(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::TwoThree(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::TwoFour(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::ThreeTwo(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::ThreeThree(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::ThreeFour(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::FourTwo(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::FourThree(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::FourFour(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::FiveTwo(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::FiveThree(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::FiveFour(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::SixTwo(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::SixThree(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::SixFour(prog) => assert_machine(prog, marks, steps, block_size),
+        ProgramT::Dynamic(prog) => assert_machine(prog, marks, steps, block_size),
+    }
+}
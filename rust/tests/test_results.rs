@@ -0,0 +1,53 @@
+mod common;
+
+use common::turing;
+
+#[test]
+fn test_results_db() {
+    let path = std::env::temp_dir().join(format!("results-db-{}.jsonl", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let programs = "1RB 1LB  1LA 1RH\n1RB 1LB  1LA 0RB\n1RB 1LA  1LA 1RB\nbad\n";
+    let batch = turing(&["batch", "--format", "json", "--limit", "100"], programs);
+    let results = String::from_utf8(batch.stdout).unwrap();
+
+    let insert = turing(&["db", "insert", path, "--params", "limit=100"], &results);
+    assert!(insert.status.success());
+
+    // Inserting the mirrored program again replaces its result.
+    let again = turing(
+        &["batch", "--format", "json", "--limit", "100", "-c", "0"],
+        "1LB 1RB  1RA 0LB\n",
+    );
+    let again = String::from_utf8(again.stdout).unwrap();
+    assert!(turing(&["db", "insert", path], &again).status.success());
+
+    let halts = turing(&["db", "query", path, "--reason", "Halt"], "");
+    assert_eq!(
+        String::from_utf8(halts.stdout).unwrap(),
+        "{\"program\":\"1RB 1LB  1LA 1RH\",\"reason\":\"Halt\",\"steps\":6,\"marks\":4,\
+         \"decider\":\"simulate\",\"parameters\":\"limit=100\"}\n"
+    );
+
+    let holdouts = turing(&["db", "holdouts", path], "");
+    assert_eq!(
+        String::from_utf8(holdouts.stdout).unwrap(),
+        "1RB 1LA  1LA 1RB\n"
+    );
+
+    let all = turing(&["db", "query", path], "");
+    assert_eq!(String::from_utf8(all.stdout).unwrap().lines().count(), 3);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_results_db_invalid() {
+    let path = std::env::temp_dir().join(format!("results-invalid-{}.jsonl", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    assert!(!turing(&["db", "insert", path], "not json\n")
+        .status
+        .success());
+    assert!(!std::path::Path::new(path).exists());
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use common::turing;
+
+fn run(args: &[&str]) -> String {
+    let output = turing(args, "");
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_run_echoes_program() {
+    assert_eq!(
+        run(&["1RB1LB_1LA1RZ", "-"]),
+        "1RB1LB_1LA1RZ: marks 4 steps 6 reason Halt\n"
+    );
+    assert_eq!(
+        run(&["1RB 1LB  1LA 1RH", "-"]),
+        "1RB 1LB  1LA 1RH: marks 4 steps 6 reason Halt\n"
+    );
+    assert!(run(&["1RB1LB_1LA1RZ", "-", "--block", "2"]).starts_with("1RB1LB_1LA1RZ: "));
+}